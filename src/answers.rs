@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::runner::Part;
+
+pub const DEFAULT_PATH: &str = "data/answers.txt";
+
+/// Expected answers, stored one per line as `<day> <part> <answer>`.
+///
+/// Blank lines and lines starting with `#` are ignored. Answers spanning
+/// several lines, such as day 10's CRT image, are written on one line with
+/// `\n` for each line break and `\\` for a literal backslash.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u8, Part), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut answers = HashMap::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, ' ');
+            let day = fields.next().and_then(|s| s.parse().ok());
+            let part = fields
+                .next()
+                .and_then(|s| s.parse().ok())
+                .and_then(Part::from_number);
+            let answer = fields.next().map(unescape);
+            match (day, part, answer) {
+                (Some(day), Some(part), Some(answer)) => {
+                    answers.insert((day, part), answer);
+                }
+                _ => return Err(format!("line {}: expected `<day> <part> <answer>`", i + 1)),
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|s| &s[..])
+    }
+}
+
+pub fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut result = String::new();
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some(c) => result.push(c),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&str>, actual: &str) -> Self {
+        match expected {
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: String::from(expected),
+                actual: String::from(actual),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected, actual } => write!(
+                f,
+                "FAIL (expected {}, got {})",
+                escape(expected),
+                escape(actual)
+            ),
+            Verdict::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("# comment\n\n01 1 66616\n10 2 #  #\\n ## \n").unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("66616"));
+        assert_eq!(answers.get(10, Part::Two), Some("#  #\n ## "));
+        assert_eq!(answers.get(1, Part::Two), None);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Answers::parse("01 1 1\n01 x 2\n").unwrap_err(),
+            "line 2: expected `<day> <part> <answer>`"
+        );
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        let answer = "a\\b\nc ";
+        assert_eq!(unescape(&escape(answer)), answer);
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::runner::Part;
+
+pub const DEFAULT_BASELINE_PATH: &str = "data/bench-baseline.txt";
+pub const DEFAULT_RUNS: usize = 10;
+
+/// How much slower than its baseline median, in percent, a stage may get
+/// before it counts as a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// A separately timed piece of a day: parsing its input, or solving a part.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part(Part::One), Stage::Part(Part::Two)];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "{}", part),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            _ => s
+                .parse()
+                .ok()
+                .and_then(Part::from_number)
+                .map(Stage::Part)
+                .ok_or_else(|| format!("invalid stage: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarizes `samples`, sorting them in place. Percentiles use the
+    /// nearest-rank method, so every figure is one of the samples.
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        Some(Stats {
+            min: samples[0],
+            median: percentile(samples, 50),
+            p95: percentile(samples, 95),
+        })
+    }
+}
+
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[derive(Clone, Debug)]
+pub struct BenchReport {
+    pub day: u8,
+    pub runs: usize,
+    pub stages: Vec<(Stage, Stats)>,
+}
+
+/// Median timings from an earlier run, stored one per line as
+/// `<day> <stage> <nanoseconds>`, where the stage is `parse`, `1` or `2`.
+///
+/// Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: HashMap<(u8, Stage), Duration>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut medians = HashMap::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split(' ');
+            let day = fields.next().and_then(|s| s.parse().ok());
+            let stage = fields.next().and_then(|s| s.parse().ok());
+            let nanos = fields.next().and_then(|s| s.parse().ok());
+            match (day, stage, nanos, fields.next()) {
+                (Some(day), Some(stage), Some(nanos), None) => {
+                    medians.insert((day, stage), Duration::from_nanos(nanos));
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: expected `<day> <stage> <nanoseconds>`",
+                        i + 1
+                    ))
+                }
+            }
+        }
+        Ok(Baseline { medians })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, day: u8, stage: Stage) -> Option<Duration> {
+        self.medians.get(&(day, stage)).copied()
+    }
+
+    pub fn record(&mut self, report: &BenchReport) {
+        for &(stage, stats) in report.stages.iter() {
+            self.medians.insert((report.day, stage), stats.median);
+        }
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Median benchmark timings: <day> <stage> <nanoseconds>")?;
+        let mut keys: Vec<_> = self.medians.keys().collect();
+        keys.sort();
+        for key in keys {
+            writeln!(f, "{:02} {} {}", key.0, key.1, self.medians[key].as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// The relative change from `baseline` to `current`, in percent.
+pub fn change(baseline: Duration, current: Duration) -> f64 {
+    (current.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+}
+
+pub fn is_regression(baseline: Duration, current: Duration, threshold: f64) -> bool {
+    change(baseline, current) > threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().map(|&m| Duration::from_millis(m)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut samples = ms(&[5, 1, 4, 2, 3, 10, 6, 7, 9, 8]);
+        let stats = Stats::from_samples(&mut samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.p95, Duration::from_millis(10));
+
+        let stats = Stats::from_samples(&mut ms(&[7])).unwrap();
+        assert_eq!(
+            (stats.min, stats.median, stats.p95),
+            (stats.p95, stats.p95, stats.p95)
+        );
+        assert_eq!(Stats::from_samples(&mut []), None);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.record(&BenchReport {
+            day: 11,
+            runs: 1,
+            stages: vec![(
+                Stage::Part(Part::Two),
+                Stats::from_samples(&mut ms(&[3])).unwrap(),
+            )],
+        });
+        let text = baseline.to_string();
+        assert!(text.ends_with("11 2 3000000\n"));
+        let parsed = Baseline::parse(&text).unwrap();
+        assert_eq!(
+            parsed.get(11, Stage::Part(Part::Two)),
+            Some(Duration::from_millis(3))
+        );
+        assert_eq!(parsed.get(11, Stage::Parse), None);
+        assert!(Baseline::parse("01 3 5\n").is_err());
+    }
+
+    #[test]
+    fn test_is_regression() {
+        let baseline = Duration::from_millis(100);
+        assert!(!is_regression(baseline, Duration::from_millis(105), 10.0));
+        assert!(is_regression(baseline, Duration::from_millis(115), 10.0));
+        assert!(!is_regression(baseline, Duration::from_millis(50), 10.0));
+    }
+}
@@ -0,0 +1,673 @@
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
+
+use adventofcode_2022_rust::answers::{self, Answers, Verdict};
+use adventofcode_2022_rust::bench::{self, Baseline, BenchReport};
+use adventofcode_2022_rust::fetch::{self, Fetched, Fetcher};
+use adventofcode_2022_rust::input::{self, Input};
+use adventofcode_2022_rust::memory::{self, AllocStats};
+use adventofcode_2022_rust::report::{self, Format};
+use adventofcode_2022_rust::runner::{self, find_day, Day, DayReport, Part, DAYS};
+use adventofcode_2022_rust::scaffold;
+use adventofcode_2022_rust::trace::{self, Filter};
+use adventofcode_2022_rust::visualize::{self, Every, ImageFormat, ImageSequence, Sink, Terminal};
+
+const USAGE: &str = "Usage:
+    aoc run <DAY> [--input <PATH>] [--part <1|2>] [--format <text|json|csv>] [--trace <FILTER>]
+    aoc all [--format <text|json|csv>] [--jobs <N>] [--trace <FILTER>]
+    aoc verify [--answers <PATH>]
+    aoc bench [<DAY>...] [--runs <N>] [--baseline <PATH>] [--save] [--threshold <PERCENT>]
+    aoc fetch <DAY> [--base-url <URL>]
+    aoc new <DAY>
+    aoc visualize <DAY> [--input <PATH>] [--to <terminal|ppm|pgm|svg>] [--out <DIR>]
+                  [--scale <N>] [--every <N>] [--delay <MS>]
+
+aoc fetch reads the session token from AOC_SESSION, and the base URL from
+AOC_BASE_URL if --base-url is not given. An --input of - reads stdin.
+Built with the alloc-stats feature, run and all also count allocations, and
+show the peak resident memory of the process while each part ran (on Linux).
+Parts then take turns, even with --jobs, so that each peak is its own.
+
+Days can trace what they are doing to stderr. A trace filter, given by
+--trace or AOC_LOG, is a level (error, warn, info, debug, trace or off) for
+every day, followed by levels for particular days: debug,day11=trace.
+
+aoc visualize plays days 9, 14, 22 and 23 in the terminal, or writes their
+frames as numbered images to --out, frames/dayNN by default.";
+
+enum CliError {
+    Usage(String),
+    Failure(String),
+}
+
+impl From<&str> for CliError {
+    fn from(message: &str) -> Self {
+        CliError::Usage(String::from(message))
+    }
+}
+
+struct Row {
+    day: u8,
+    part: String,
+    answer: String,
+    elapsed: Option<Duration>,
+    alloc: Option<AllocStats>,
+}
+
+fn report_rows(report: &DayReport) -> Vec<Row> {
+    let mut rows = vec![Row {
+        day: report.day,
+        part: String::from("parse"),
+        answer: String::new(),
+        elapsed: Some(report.parse_time),
+        alloc: report.parse_alloc,
+    }];
+    for part in report.parts.iter() {
+        rows.push(Row {
+            day: report.day,
+            part: part.part.to_string(),
+            answer: part.answer.clone(),
+            elapsed: Some(part.elapsed),
+            alloc: part.alloc,
+        });
+    }
+    rows
+}
+
+fn print_table(rows: &[Row], total: Duration) {
+    let answer_width = rows
+        .iter()
+        .flat_map(|r| r.answer.lines())
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    // Allocation columns only appear in builds that count them.
+    let alloc_header = if memory::ENABLED {
+        format!("  {:>10}  {:>10}  {:>10}", "Allocs", "Bytes", "Peak RSS")
+    } else {
+        String::new()
+    };
+    let header = format!(
+        "Day  Part   {:<answer_width$}  {:<11}{}",
+        "Answer", "Time", alloc_header
+    );
+    println!("{}", header.trim_end());
+    for row in rows.iter() {
+        let mut lines = row.answer.lines();
+        let first = lines.next().unwrap_or("");
+        let elapsed = match row.elapsed {
+            Some(elapsed) => format!("{:.3?}", elapsed),
+            None => String::from("-"),
+        };
+        let alloc = match row.alloc {
+            Some(a) => format!(
+                "  {:>10}  {:>10}  {:>10}",
+                a.allocations,
+                format_bytes(a.bytes),
+                a.peak_resident_bytes
+                    .map_or_else(|| String::from("-"), format_bytes)
+            ),
+            None => String::new(),
+        };
+        let line = format!(
+            "{:>3}  {:<5}  {:<answer_width$}  {:<11}{}",
+            row.day, row.part, first, elapsed, alloc
+        );
+        println!("{}", line.trim_end());
+        for line in lines {
+            println!("{:>3}  {:<5}  {}", "", "", line);
+        }
+    }
+    println!("{:<12}{:<answer_width$}  {:.3?}", "Total", "", total);
+    if memory::ENABLED {
+        println!("Allocations are counted on the thread running each part only. The peak");
+        println!("RSS is the most memory the whole process had resident while it ran.");
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn print_reports(reports: &[DayReport], format: Format) -> io::Result<()> {
+    let records = reports.iter().flat_map(report::records);
+    let mut out = io::stdout().lock();
+    match format {
+        Format::Json => report::write_json(&mut out, records),
+        Format::Csv => report::write_csv(&mut out, records),
+        Format::Text => {
+            let rows: Vec<Row> = reports.iter().flat_map(report_rows).collect();
+            print_table(&rows, reports.iter().map(|r| r.total_time()).sum());
+            Ok(())
+        }
+    }
+}
+
+fn parse_format(value: Option<&String>) -> Result<Format, CliError> {
+    let value = value.ok_or("--format needs a value")?;
+    value.parse().map_err(CliError::Usage)
+}
+
+fn parse_trace(value: Option<&String>) -> Result<(), CliError> {
+    let value = value.ok_or("--trace needs a filter")?;
+    let filter: Filter = value.parse().map_err(CliError::Usage)?;
+    trace::set_filter(filter);
+    Ok(())
+}
+
+/// Picks `--input` out of `args` the same way as the day binaries do, leaving
+/// it to the command to find the day's own input if there is none.
+fn input_arg(args: &[String], usage: &str) -> Result<(Option<PathBuf>, Vec<String>), CliError> {
+    input::optional_input_arg(args.iter().cloned(), usage)
+        .map_err(|e| CliError::Usage(e.to_string()))
+}
+
+fn read_input(day: &Day, path: Option<PathBuf>) -> Result<Input, String> {
+    let path = path.unwrap_or_else(|| day.input_path());
+    let input = if path == Path::new("-") {
+        Input::from_stdin()
+    } else {
+        Input::mmap(&path)
+    };
+    input.map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn run(args: &[String]) -> Result<(), CliError> {
+    let (input, args) = input_arg(args, "usage: aoc run <DAY> [--input <PATH>] ...")?;
+    let mut day = None;
+    let mut parts = Part::BOTH.to_vec();
+    let mut format = Format::Text;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = args.next().ok_or("--part needs a value")?;
+                let part = part
+                    .parse()
+                    .ok()
+                    .and_then(Part::from_number)
+                    .ok_or_else(|| CliError::Usage(format!("invalid part: {}", part)))?;
+                parts = vec![part];
+            }
+            "--format" => format = parse_format(args.next())?,
+            "--trace" => parse_trace(args.next())?,
+            _ if day.is_none() => {
+                let number = arg
+                    .parse()
+                    .map_err(|_| CliError::Usage(format!("invalid day: {}", arg)))?;
+                let found = find_day(number)
+                    .ok_or_else(|| CliError::Failure(format!("day {} is not implemented", arg)))?;
+                day = Some(found);
+            }
+            _ => return Err(CliError::Usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+
+    let day = day.ok_or("missing day number")?;
+    let path = input.unwrap_or_else(|| day.input_path());
+    let input = read_input(day, Some(path.clone())).map_err(CliError::Failure)?;
+    let report = day
+        .run(&input, &parts)
+        .map_err(|e| CliError::Failure(format!("{}: {}", path.display(), e)))?;
+    print_reports(&[report], format).map_err(|e| CliError::Failure(e.to_string()))
+}
+
+fn all(args: &[String]) -> Result<(), CliError> {
+    let mut format = Format::Text;
+    let mut jobs = 1;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = parse_format(args.next())?,
+            "--trace" => parse_trace(args.next())?,
+            "--jobs" => {
+                let value = args.next().ok_or("--jobs needs a value")?;
+                jobs = value
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| CliError::Usage(format!("invalid job count: {}", value)))?;
+            }
+            _ => return Err(CliError::Usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+
+    let start = Instant::now();
+    let outcomes = runner::run_parallel(DAYS, jobs, |day| {
+        let input = read_input(day, None).map_err(|e| format!("missing input ({})", e))?;
+        day.run(&input, &Part::BOTH)
+            .map_err(|e| format!("parse error ({})", e))
+    });
+    let wall_time = start.elapsed();
+
+    let mut reports = Vec::new();
+    let mut rows = Vec::new();
+    let mut failed = Vec::new();
+    let mut total = Duration::ZERO;
+    for outcome in outcomes {
+        match outcome.result {
+            Ok(report) => {
+                total += report.total_time();
+                rows.append(&mut report_rows(&report));
+                reports.push(report);
+            }
+            Err(problem) => {
+                // Machine-readable output only has room for answers, so
+                // problems go to stderr instead.
+                if format != Format::Text {
+                    eprintln!("day {:02}: {}", outcome.day, problem);
+                }
+                failed.push(format!("{:02}", outcome.day));
+                rows.push(Row {
+                    day: outcome.day,
+                    part: String::from("-"),
+                    answer: problem,
+                    elapsed: None,
+                    alloc: None,
+                });
+            }
+        }
+        rows.push(Row {
+            day: outcome.day,
+            part: String::from("wall"),
+            answer: String::new(),
+            elapsed: Some(outcome.wall_time),
+            alloc: None,
+        });
+    }
+
+    if format == Format::Text {
+        print_table(&rows, total);
+        let jobs = match jobs {
+            1 => String::from("1 job"),
+            n => format!("{} jobs", n),
+        };
+        println!("Wall time: {:.3?} ({})", wall_time, jobs);
+    } else {
+        print_reports(&reports, format).map_err(|e| CliError::Failure(e.to_string()))?;
+    }
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Failure(format!(
+            "failed days: {}",
+            failed.join(", ")
+        )))
+    }
+}
+
+fn verify(args: &[String]) -> Result<(), CliError> {
+    let mut path = PathBuf::from(answers::DEFAULT_PATH);
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                let arg = args.next().ok_or("--answers needs a path")?;
+                path = PathBuf::from(arg);
+            }
+            _ => return Err(CliError::Usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+
+    let expected = Answers::load(&path).map_err(CliError::Failure)?;
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for day in DAYS.iter() {
+        let input_path = day.input_path();
+        let verdicts: Vec<(Part, Verdict)> = match Input::mmap(&input_path) {
+            Ok(input) => {
+                let report = match day.run(&input, &Part::BOTH) {
+                    Ok(report) => report,
+                    Err(e) => {
+                        failed += Part::BOTH.len();
+                        println!("Day {:02}: ERROR ({})", day.number, e);
+                        continue;
+                    }
+                };
+                report
+                    .parts
+                    .iter()
+                    .map(|p| {
+                        let verdict = Verdict::check(expected.get(day.number, p.part), &p.answer);
+                        (p.part, verdict)
+                    })
+                    .collect()
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Part::BOTH.iter().map(|&p| (p, Verdict::Missing)).collect()
+            }
+            // An input that is there but cannot be read is not missing.
+            Err(e) => {
+                failed += Part::BOTH.len();
+                println!(
+                    "Day {:02}: ERROR ({}: {})",
+                    day.number,
+                    input_path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+        for (part, verdict) in verdicts.iter() {
+            match verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Fail { .. } => failed += 1,
+                Verdict::Missing => missing += 1,
+            }
+            println!("Day {:02} part {}: {}", day.number, part, verdict);
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        return Err(CliError::Failure(format!(
+            "{} answer(s) did not match {}",
+            failed,
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+fn print_bench(report: &BenchReport, baseline: &Baseline, threshold: f64) -> usize {
+    let mut regressions = 0;
+    for &(stage, stats) in report.stages.iter() {
+        let (previous, change) = match baseline.get(report.day, stage) {
+            Some(previous) => {
+                let mut change = format!("{:+.1}%", bench::change(previous, stats.median));
+                if bench::is_regression(previous, stats.median, threshold) {
+                    regressions += 1;
+                    change.push_str(" REGRESSION");
+                }
+                (format!("{:.3?}", previous), change)
+            }
+            None => (String::from("-"), String::from("-")),
+        };
+        println!(
+            "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}  {:>12}  {}",
+            report.day,
+            stage,
+            format!("{:.3?}", stats.min),
+            format!("{:.3?}", stats.median),
+            format!("{:.3?}", stats.p95),
+            previous,
+            change
+        );
+    }
+    regressions
+}
+
+fn bench(args: &[String]) -> Result<(), CliError> {
+    let mut days = Vec::new();
+    let mut runs = bench::DEFAULT_RUNS;
+    let mut path = PathBuf::from(bench::DEFAULT_BASELINE_PATH);
+    let mut save = false;
+    let mut threshold = bench::DEFAULT_THRESHOLD;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => {
+                let arg = args.next().ok_or("--runs needs a value")?;
+                runs =
+                    arg.parse().ok().filter(|&n| n > 0).ok_or_else(|| {
+                        CliError::Usage(format!("invalid number of runs: {}", arg))
+                    })?;
+            }
+            "--baseline" => {
+                let arg = args.next().ok_or("--baseline needs a path")?;
+                path = PathBuf::from(arg);
+            }
+            "--save" => save = true,
+            "--threshold" => {
+                let arg = args.next().ok_or("--threshold needs a percentage")?;
+                threshold = arg
+                    .parse()
+                    .ok()
+                    .filter(|t: &f64| *t >= 0.0)
+                    .ok_or_else(|| CliError::Usage(format!("invalid threshold: {}", arg)))?;
+            }
+            _ => {
+                let number = arg
+                    .parse()
+                    .map_err(|_| CliError::Usage(format!("unexpected argument: {}", arg)))?;
+                let day = find_day(number)
+                    .ok_or_else(|| CliError::Failure(format!("day {} is not implemented", arg)))?;
+                days.push(day);
+            }
+        }
+    }
+    if days.is_empty() {
+        days = DAYS.iter().collect();
+    }
+
+    let mut baseline = if path.exists() {
+        Baseline::load(&path).map_err(CliError::Failure)?
+    } else {
+        Baseline::default()
+    };
+
+    println!(
+        "Day  Stage  {:>12}  {:>12}  {:>12}  {:>12}  Change",
+        "Min", "Median", "P95", "Baseline"
+    );
+    let mut reports = Vec::new();
+    let mut regressions = 0;
+    for day in days {
+        let input = match read_input(day, None) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{:>3}  missing input ({})", day.number, e);
+                continue;
+            }
+        };
+        let report = day
+            .bench(&input, runs)
+            .map_err(|e| CliError::Failure(format!("day {:02}: {}", day.number, e)))?;
+        regressions += print_bench(&report, &baseline, threshold);
+        reports.push(report);
+    }
+
+    if save {
+        for report in reports.iter() {
+            baseline.record(report);
+        }
+        baseline.save(&path).map_err(CliError::Failure)?;
+        println!("Saved baseline to {}", path.display());
+    }
+    if regressions > 0 {
+        return Err(CliError::Failure(format!(
+            "{} stage(s) regressed more than {}% against {}",
+            regressions,
+            threshold,
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+fn fetch(args: &[String]) -> Result<(), CliError> {
+    let mut day = None;
+    let mut base_url = env::var(fetch::BASE_URL_VAR).ok();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--base-url" => {
+                let arg = args.next().ok_or("--base-url needs a URL")?;
+                base_url = Some(arg.clone());
+            }
+            _ if day.is_none() => {
+                let number = arg
+                    .parse()
+                    .ok()
+                    .filter(|n| (1..=25).contains(n))
+                    .ok_or_else(|| CliError::Usage(format!("invalid day: {}", arg)))?;
+                day = Some(number);
+            }
+            _ => return Err(CliError::Usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+
+    let day = day.ok_or("missing day number")?;
+    let path = runner::input_path(day);
+    let session = env::var(fetch::SESSION_VAR).unwrap_or_default();
+    if session.trim().is_empty() && !path.exists() {
+        return Err(CliError::Failure(format!(
+            "no session token: set {} to your adventofcode.com session cookie",
+            fetch::SESSION_VAR
+        )));
+    }
+    let base_url = base_url.as_deref().unwrap_or(fetch::DEFAULT_BASE_URL);
+    let fetcher = Fetcher::new(base_url, &session);
+    match fetcher.fetch(day, &path) {
+        Ok(Fetched::Cached) => println!("Day {:02}: already cached in {}", day, path.display()),
+        Ok(Fetched::Downloaded) => println!("Day {:02}: saved to {}", day, path.display()),
+        Err(e) => return Err(CliError::Failure(format!("day {:02}: {}", day, e))),
+    }
+    Ok(())
+}
+
+fn new(args: &[String]) -> Result<(), CliError> {
+    let day = match args {
+        [day] => day,
+        [] => return Err(CliError::from("missing day number")),
+        [_, arg, ..] => return Err(CliError::Usage(format!("unexpected argument: {}", arg))),
+    };
+    let day = day
+        .parse()
+        .ok()
+        .filter(|n| (1..=25).contains(n))
+        .ok_or_else(|| CliError::Usage(format!("invalid day: {}", day)))?;
+
+    let written = scaffold::scaffold(Path::new("."), day).map_err(CliError::Failure)?;
+    for path in written {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn parse_count(flag: &str, value: Option<&String>) -> Result<usize, CliError> {
+    let value = value.ok_or_else(|| CliError::Usage(format!("{} needs a value", flag)))?;
+    value
+        .parse()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| CliError::Usage(format!("invalid {} value: {}", flag, value)))
+}
+
+fn visualize(args: &[String]) -> Result<(), CliError> {
+    let (input, args) = input_arg(args, "usage: aoc visualize <DAY> [--input <PATH>] ...")?;
+    let mut visual = None;
+    let mut format = None;
+    let mut out = None;
+    let mut scale = 4;
+    let mut every = 1;
+    let mut delay = Duration::from_millis(50);
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--to" => {
+                let value = args.next().ok_or("--to needs a value")?;
+                format = match value.as_str() {
+                    "terminal" => None,
+                    "ppm" => Some(ImageFormat::Ppm),
+                    "pgm" => Some(ImageFormat::Pgm),
+                    "svg" => Some(ImageFormat::Svg),
+                    _ => return Err(CliError::Usage(format!("invalid output: {}", value))),
+                };
+            }
+            "--out" => {
+                let dir = args.next().ok_or("--out needs a directory")?;
+                out = Some(PathBuf::from(dir));
+            }
+            "--scale" => scale = parse_count("--scale", args.next())?,
+            "--every" => every = parse_count("--every", args.next())?,
+            "--delay" => {
+                let value = args.next().ok_or("--delay needs a value")?;
+                let ms = value
+                    .parse()
+                    .map_err(|_| CliError::Usage(format!("invalid delay: {}", value)))?;
+                delay = Duration::from_millis(ms);
+            }
+            _ if visual.is_none() => {
+                let number = arg
+                    .parse()
+                    .map_err(|_| CliError::Usage(format!("invalid day: {}", arg)))?;
+                let found = visualize::find_visual(number).ok_or_else(|| {
+                    CliError::Failure(format!("day {} has no visualization", arg))
+                })?;
+                visual = Some(found);
+            }
+            _ => return Err(CliError::Usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+
+    let visual = visual.ok_or("missing day number")?;
+    let day = find_day(visual.number).expect("every visualized day is registered");
+    let path = input.unwrap_or_else(|| day.input_path());
+    let input = read_input(day, Some(path.clone())).map_err(CliError::Failure)?;
+    let sink: Box<dyn Sink> = match format {
+        None => Box::new(Terminal::new(io::stdout().lock(), delay)),
+        Some(format) => {
+            let dir = out.unwrap_or_else(|| PathBuf::from(format!("frames/day{:02}", day.number)));
+            let images = ImageSequence::new(dir.clone(), format, scale)
+                .map_err(|e| CliError::Failure(format!("{}: {}", dir.display(), e)))?;
+            Box::new(images)
+        }
+    };
+    visual
+        .run(&input, &mut Every::new(sink, every))
+        .map_err(|e| CliError::Failure(format!("{}: {}", path.display(), e)))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = trace::init_from_env() {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    }
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("all") => all(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("visualize") => visualize(&args[1..]),
+        Some(command) => Err(CliError::Usage(format!("unknown command: {}", command))),
+        None => Err(CliError::from("missing command")),
+    };
+
+    match result {
+        Ok(()) => (),
+        Err(CliError::Usage(e)) => {
+            eprintln!("error: {}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+        Err(CliError::Failure(e)) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -1,26 +1,172 @@
+use std::env;
 use std::io;
-use std::io::prelude::*;
+use std::path::PathBuf;
 
-fn main() -> io::Result<()> {
-    let mut input = String::new();
-    io::stdin().lock().read_to_string(&mut input).unwrap();
+use adventofcode_2022_rust::day01::{self, Day01, Elf, Elves, Inventories, Summary, Top};
+use adventofcode_2022_rust::error::Error;
+use adventofcode_2022_rust::input::{self, Input};
+use adventofcode_2022_rust::report::Format;
+use adventofcode_2022_rust::Solution;
 
-    let mut input: Vec<u64> = input
-        .split("\n\n")
-        .filter(|s| s.len() > 0)
-        .map(|s| s.split('\n').map(|s| s.parse().unwrap()).collect())
-        .map(|v: Vec<u64>| v.iter().sum::<u64>())
-        .collect();
-    input.sort();
-    input.reverse();
+const USAGE: &str = "usage: [--input <path>] \
+    [--top <N> | --teams <K> | --summary [--format <text|csv>] [--bins <N>]]";
+
+/// What to do with the elves: answer the puzzle, or one of the other
+/// queries.
+enum Mode {
+    Answers,
+    Top(usize),
+    Teams(usize),
+    Summary,
+}
+
+fn usage(message: String) -> Error {
+    Error::Io(input::usage_error(message, USAGE))
+}
+
+/// Streams the elves from `path`, keeping only the `n` carrying the most.
+fn print_top(path: PathBuf, n: usize) -> Result<(), Error> {
+    let mut top = Top::new(n);
+    for elf in Elves::new(input::open(&path)?) {
+        top.push(elf?);
+    }
+    let ranked = top.into_ranked();
+    if ranked.is_empty() {
+        println!("No elves to rank.");
+        return Ok(());
+    }
+
+    println!("Rank  Elf  Items  Calories");
+    for (rank, elf) in ranked.iter().enumerate() {
+        println!(
+            "{:>4}  {:>3}  {:>5}  {:>8}",
+            rank + 1,
+            elf.index,
+            elf.items,
+            elf.calories
+        );
+    }
+    let total: u64 = ranked.iter().map(|elf| elf.calories).sum();
+    if ranked.len() < n {
+        println!("Only {} elves, carrying {} in all", ranked.len(), total);
+    } else {
+        println!("The top {} are carrying {} in all", n, total);
+    }
+    Ok(())
+}
+
+/// Splits the elves from `path` into `k` teams as evenly as it can.
+fn print_teams(path: PathBuf, k: usize) -> Result<(), Error> {
+    let elves: Vec<Elf> = Elves::new(input::open(&path)?).collect::<Result<_, _>>()?;
+    let teams = day01::balance(&elves, k);
+
+    println!("Team     Total  Elves");
+    for (i, (total, team)) in teams.teams.iter().enumerate() {
+        let team: Vec<String> = team.iter().map(|i| i.to_string()).collect();
+        let team = if team.is_empty() {
+            String::from("-")
+        } else {
+            team.join(", ")
+        };
+        println!("{:>4}  {:>8}  {}", i + 1, total, team);
+    }
+    let optimal = if teams.optimal {
+        "optimal"
+    } else {
+        "may not be optimal"
+    };
+    println!(
+        "Split by {} ({}): heaviest {}, lightest {}, imbalance {}",
+        teams.method,
+        optimal,
+        teams.heaviest(),
+        teams.lightest(),
+        teams.imbalance()
+    );
+    println!(
+        "No split can have a heaviest team under {}",
+        day01::lower_bound(&elves, k)
+    );
+    Ok(())
+}
+
+/// Streams the inventories from `path` into per-elf and per-item totals.
+fn print_summary(path: PathBuf, format: Format, bins: usize) -> Result<(), Error> {
+    let mut summary = Summary::default();
+    for inventory in Inventories::new(input::open(&path)?) {
+        summary.add(&inventory?);
+    }
+    let mut out = io::stdout().lock();
+    match format {
+        Format::Csv => day01::write_summary_csv(&mut out, &summary, bins)?,
+        _ => day01::write_summary_text(&mut out, &summary, bins)?,
+    }
+    Ok(())
+}
+
+fn count_arg(flag: &str, value: Option<String>) -> Result<usize, Error> {
+    let value = value.ok_or_else(|| usage(format!("{} needs a count", flag)))?;
+    value
+        .parse()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| usage(format!("invalid {} count: {}", flag, value)))
+}
+
+fn choose(mode: &mut Mode, chosen: Mode) -> Result<(), Error> {
+    match mode {
+        Mode::Answers => {
+            *mode = chosen;
+            Ok(())
+        }
+        _ => Err(usage(String::from(
+            "only one of --top, --teams and --summary can be given",
+        ))),
+    }
+}
+
+fn main() -> Result<(), Error> {
+    let (path, args) = input::input_arg(env::args().skip(1), USAGE)?;
+    let mut mode = Mode::Answers;
+    let mut format = None;
+    let mut bins = 10;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => choose(&mut mode, Mode::Top(count_arg("--top", args.next())?))?,
+            "--teams" => choose(&mut mode, Mode::Teams(count_arg("--teams", args.next())?))?,
+            "--summary" => choose(&mut mode, Mode::Summary)?,
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage(String::from("--format needs a value")))?;
+                format = match value.parse() {
+                    Ok(f @ (Format::Text | Format::Csv)) => Some(f),
+                    _ => return Err(usage(format!("invalid format: {}", value))),
+                };
+            }
+            "--bins" => bins = count_arg("--bins", args.next())?,
+            _ => return Err(usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+    match mode {
+        Mode::Summary => return print_summary(path, format.unwrap_or(Format::Text), bins),
+        _ if format.is_some() => return Err(usage(String::from("--format needs --summary"))),
+        Mode::Top(n) => return print_top(path, n),
+        Mode::Teams(k) => return print_teams(path, k),
+        Mode::Answers => (),
+    }
+
+    let input = Input::from_arg(&path)?;
+    let input = Day01::parse(&input)?;
 
     println!(
         "The elf carrying the most calories is carrying: {}",
-        input[0]
+        Day01::part_one(&input)
     );
     println!(
         "The three elves carrying the most calories are carrying: {}",
-        input[0] + input[1] + input[2]
+        Day01::part_two(&input)
     );
 
     Ok(())
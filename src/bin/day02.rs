@@ -1,128 +1,115 @@
+use std::env;
+use std::fs;
 use std::io;
-use std::io::prelude::*;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug)]
-enum Outcome {
-    Win,
-    Loss,
-    Draw,
+use adventofcode_2022_rust::day02::{Decoding, Guide, Rules};
+use adventofcode_2022_rust::error::Error;
+use adventofcode_2022_rust::input::{self, Input};
+
+const USAGE: &str = "usage: [--input <path>] [--rules <path>] [--decode [--expect <score>]]";
+
+fn usage(message: String) -> Error {
+    Error::Io(input::usage_error(message, USAGE))
 }
 
-#[derive(Debug)]
-enum Rps {
-    Rock,
-    Paper,
-    Scissors,
+fn invalid(message: String) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, message))
 }
 
-impl Rps {
-    fn game_from(s: &&str) -> Game {
-        let r = match s.chars().nth(0).unwrap() {
-            'A' => Rps::Rock,
-            'B' => Rps::Paper,
-            'C' => Rps::Scissors,
-            _ => unreachable!(),
-        };
-        let s = match s.chars().nth(2).unwrap() {
-            'X' => Rps::Rock,
-            'Y' => Rps::Paper,
-            'Z' => Rps::Scissors,
-            _ => unreachable!(),
-        };
-        (r, s)
-    }
+/// Reads the rules from `path`, naming the file in any error.
+fn read_rules(path: &Path) -> Result<Rules, Error> {
+    let config = fs::read_to_string(path)?;
+    Rules::parse(&config).map_err(|e| invalid(format!("{}: {}", path.display(), e)))
+}
 
-    fn game_from_part2(s: &&str) -> Game {
-        let r = match s.chars().nth(0).unwrap() {
-            'A' => Rps::Rock,
-            'B' => Rps::Paper,
-            'C' => Rps::Scissors,
-            _ => unreachable!(),
-        };
-        let desired_outcome = match s.chars().nth(2).unwrap() {
-            'X' => Outcome::Loss,
-            'Y' => Outcome::Draw,
-            'Z' => Outcome::Win,
-            _ => unreachable!(),
-        };
-        let s = r.get_move_for_outcome(&desired_outcome);
-        (r, s)
+/// Lists the mappings that give `score`, or says there are none.
+fn print_matching(label: &str, score: u64, decoding: &Decoding, rules: &Rules) {
+    let mappings = decoding.matching(score);
+    if mappings.is_empty() {
+        println!("{} {}: no mapping", label, score);
+    }
+    for mapping in mappings {
+        let described = mapping.describe(rules);
+        println!("{} {}: {} as {}", label, score, described, mapping.kind());
     }
+}
 
-    fn get_move_for_outcome(&self, outcome: &Outcome) -> Rps {
-        match self {
-            Rps::Rock => match outcome {
-                Outcome::Win => Rps::Paper,
-                Outcome::Loss => Rps::Scissors,
-                Outcome::Draw => Rps::Rock,
-            },
-            Rps::Paper => match outcome {
-                Outcome::Win => Rps::Scissors,
-                Outcome::Loss => Rps::Rock,
-                Outcome::Draw => Rps::Paper,
-            },
-            Rps::Scissors => match outcome {
-                Outcome::Win => Rps::Rock,
-                Outcome::Loss => Rps::Paper,
-                Outcome::Draw => Rps::Scissors,
-            },
+/// Scores the guide under every mapping of its second column.
+fn print_decoding(guide: &Guide, expected: Option<u64>) {
+    let decoding = guide.decode();
+    println!("Reading     Score  Mapping");
+    for (mapping, score) in decoding.readings.iter() {
+        let described = mapping.describe(&guide.rules);
+        match score {
+            Ok(score) => println!("{:<8}  {:>7}  {}", mapping.kind(), score, described),
+            Err(e) => println!("{:<8}  {:>7}  {} ({})", mapping.kind(), "-", described, e),
         }
     }
-
-    fn outcome(&self, other: &Self) -> Outcome {
-        match self {
-            Rps::Rock => match other {
-                Rps::Rock => Outcome::Draw,
-                Rps::Paper => Outcome::Loss,
-                Rps::Scissors => Outcome::Win,
-            },
-            Rps::Paper => match other {
-                Rps::Rock => Outcome::Win,
-                Rps::Paper => Outcome::Draw,
-                Rps::Scissors => Outcome::Loss,
-            },
-            Rps::Scissors => match other {
-                Rps::Rock => Outcome::Loss,
-                Rps::Paper => Outcome::Win,
-                Rps::Scissors => Outcome::Draw,
-            },
+    match (decoding.best(), decoding.worst()) {
+        (Some(best), Some(worst)) => {
+            print_matching("Best", best, &decoding, &guide.rules);
+            print_matching("Worst", worst, &decoding, &guide.rules);
         }
+        _ => println!("The guide cannot be followed under any mapping."),
     }
-
-    fn score(&self, other: &Self) -> u64 {
-        let score = match self {
-            Rps::Rock => 1,
-            Rps::Paper => 2,
-            Rps::Scissors => 3,
-        };
-        score
-            + match self.outcome(&other) {
-                Outcome::Win => 6,
-                Outcome::Loss => 0,
-                Outcome::Draw => 3,
-            }
+    if let Some(expected) = expected {
+        print_matching("Expected", expected, &decoding, &guide.rules);
     }
 }
 
-type Game = (Rps, Rps);
-
-fn get_total_score(games: &Vec<Game>) -> u64 {
-    games.iter().map(|(a, b)| b.score(&a)).sum::<u64>()
-}
-
-fn main() -> io::Result<()> {
-    let mut input = String::new();
-
-    io::stdin().lock().read_to_string(&mut input).unwrap();
-    let input: Vec<&str> = input.split('\n').filter(|s| s.len() > 0).collect();
+fn main() -> Result<(), Error> {
+    let (path, args) = input::input_arg(env::args().skip(1), USAGE)?;
+    let mut rules = None;
+    let mut decode = false;
+    let mut expected = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rules" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage(String::from("--rules needs a path")))?;
+                rules = Some(PathBuf::from(value));
+            }
+            "--decode" => decode = true,
+            "--expect" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage(String::from("--expect needs a score")))?;
+                let score = value
+                    .parse()
+                    .map_err(|_| usage(format!("invalid --expect score: {}", value)))?;
+                expected = Some(score);
+            }
+            _ => return Err(usage(format!("unexpected argument: {}", arg))),
+        }
+    }
+    if expected.is_some() && !decode {
+        return Err(usage(String::from("--expect needs --decode")));
+    }
+    let rules = match rules {
+        Some(rules) => read_rules(&rules)?,
+        None => Rules::standard(),
+    };
 
-    let input_part1 = input.iter().map(Rps::game_from).collect();
-    let score_part_1 = get_total_score(&input_part1);
-    println!("The score according to your guide: {}", score_part_1);
+    let input = Input::from_arg(&path)?;
+    let guide = Guide::parse(&input, rules)?;
+    if decode {
+        print_decoding(&guide, expected);
+        return Ok(());
+    }
 
-    let input_part2 = input.iter().map(Rps::game_from_part2).collect();
-    let score_part_2 = get_total_score(&input_part2);
-    println!("The score according to their guide: {}", score_part_2);
+    // Under custom rules, a code may stand for a shape but not an outcome,
+    // or an outcome may be impossible against some shape.
+    match guide.score_by_shape() {
+        Ok(score) => println!("The score according to your guide: {}", score),
+        Err(e) => println!("Your guide cannot be followed: {}", e),
+    }
+    match guide.score_by_outcome() {
+        Ok(score) => println!("The score according to their guide: {}", score),
+        Err(e) => println!("Their guide cannot be followed: {}", e),
+    }
 
     Ok(())
 }
@@ -1,43 +1,18 @@
-use std::io;
-use std::io::prelude::*;
+use std::env;
 
-use nom::bytes::complete::take;
-use nom::character::complete::anychar;
-use nom::combinator::{recognize, verify};
-use nom::multi::many_till;
-use nom::IResult;
+use adventofcode_2022_rust::day06::Day06;
+use adventofcode_2022_rust::error::Error;
+use adventofcode_2022_rust::input::Input;
+use adventofcode_2022_rust::Solution;
 
-fn all_different(input: &str) -> bool {
-    let l = input.len();
-    let input = input.as_bytes();
-    for i in 0..l {
-        for j in i + 1..l {
-            if &input[i] == &input[j] {
-                return false;
-            }
-        }
-    }
-    true
-}
-
-fn marker_parser(marker_len: usize) -> impl Fn(&str) -> IResult<&str, &str> {
-    move |input: &str| verify(take(marker_len), all_different)(input)
-}
-
-fn length_before_marker(input: &str, marker_len: usize) -> usize {
-    let (_, prefix) = recognize(many_till(anychar, marker_parser(marker_len)))(input).unwrap();
-    return prefix.len();
-}
-
-fn main() -> io::Result<()> {
-    let mut input = String::new();
-    io::stdin().lock().read_to_string(&mut input).unwrap();
-    let input = &input[..];
+fn main() -> Result<(), Error> {
+    let input = Input::from_args(env::args().skip(1))?;
+    let input = Day06::parse(&input)?;
 
-    let l = length_before_marker(input, 4);
+    let l = Day06::part_one(&input);
     println!("Length before the start-of-packet marker: {}", l);
 
-    let l = length_before_marker(input, 14);
+    let l = Day06::part_two(&input);
     println!("Length before the start-of-message marker: {}", l);
 
     Ok(())
@@ -0,0 +1,894 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+
+use ahash::{AHashMap, AHashSet};
+
+use crate::error::{Error, ParseError};
+use crate::report;
+use crate::Solution;
+
+/// One line of an inventory: a bare calorie count, or one with the name of
+/// the item, as in `apple 300` or `ration:1200`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Item {
+    pub name: Option<String>,
+    pub calories: u64,
+}
+
+fn parse_item(line: &str, line_number: usize) -> Result<Item, ParseError> {
+    let error = |at: usize, expected: &str| {
+        ParseError::new(line_number, line[..at].chars().count() + 1, expected)
+    };
+    let start = line.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let (name, count) = line.split_at(start);
+    if count.is_empty() {
+        let bad = line.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+        return Err(error(bad, "a calorie count or a blank line"));
+    }
+    let calories = count
+        .parse()
+        .map_err(|_| error(start, "a calorie count in range"))?;
+    if name.is_empty() {
+        return Ok(Item {
+            name: None,
+            calories,
+        });
+    }
+
+    let separated = name.trim_end();
+    let name = match separated.strip_suffix(':') {
+        Some(name) => name.trim_end(),
+        None if separated.len() < name.len() => separated,
+        None => return Err(error(start, "\":\" or a space before the count")),
+    };
+    let name = name.trim_start();
+    if name.is_empty() {
+        return Err(error(0, "an item name"));
+    }
+    Ok(Item {
+        name: Some(String::from(name)),
+        calories,
+    })
+}
+
+/// Everything one elf carries.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Inventory {
+    /// Where the elf comes in the input, counting from 0.
+    pub index: usize,
+    pub items: Vec<Item>,
+}
+
+impl Inventory {
+    pub fn elf(&self) -> Elf {
+        Elf {
+            index: self.index,
+            calories: self.items.iter().map(|item| item.calories).sum(),
+            items: self.items.len(),
+        }
+    }
+}
+
+/// Reads inventories one at a time from blank line separated groups of
+/// items, so that an input never has to be held in memory all at once.
+///
+/// The calories of all the elves together must fit in a `u64`. Every total
+/// taken of them, for an elf, an item, the top elves or a team, is part of
+/// that, so none of them can overflow.
+pub struct Inventories<R> {
+    lines: io::Lines<R>,
+    line_number: usize,
+    index: usize,
+    total: u64,
+}
+
+impl<R: BufRead> Inventories<R> {
+    pub fn new(reader: R) -> Self {
+        Inventories {
+            lines: reader.lines(),
+            line_number: 0,
+            index: 0,
+            total: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Inventories<R> {
+    type Item = Result<Inventory, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut items = Vec::new();
+        for line in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            self.line_number += 1;
+            if line.is_empty() {
+                if !items.is_empty() {
+                    break;
+                }
+                continue;
+            }
+            let item = match parse_item(&line, self.line_number) {
+                Ok(item) => item,
+                Err(e) => return Some(Err(e.into())),
+            };
+            match self.total.checked_add(item.calories) {
+                Some(total) => self.total = total,
+                None => {
+                    let digits = line.trim_end_matches(|c: char| c.is_ascii_digit());
+                    let column = digits.chars().count() + 1;
+                    let expected = "a calorie count keeping the total of all elves in range";
+                    return Some(Err(
+                        ParseError::new(self.line_number, column, expected).into()
+                    ));
+                }
+            }
+            items.push(item);
+        }
+        if items.is_empty() {
+            return None;
+        }
+        let index = self.index;
+        self.index += 1;
+        Some(Ok(Inventory { index, items }))
+    }
+}
+
+/// One elf's inventory, added up.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Elf {
+    /// Where the elf comes in the input, counting from 0.
+    pub index: usize,
+    pub calories: u64,
+    pub items: usize,
+}
+
+/// Reads elves one at a time, adding up each inventory as it goes.
+pub struct Elves<R> {
+    inventories: Inventories<R>,
+}
+
+impl<R: BufRead> Elves<R> {
+    pub fn new(reader: R) -> Self {
+        Elves {
+            inventories: Inventories::new(reader),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inventories
+            .next()
+            .map(|inventory| inventory.map(|i| i.elf()))
+    }
+}
+
+/// An elf ranked by what it carries, the first of equal elves ranking
+/// higher.
+#[derive(Eq, PartialEq)]
+struct Ranked(Elf);
+
+impl Ranked {
+    fn key(&self) -> (u64, Reverse<usize>) {
+        (self.0.calories, Reverse(self.0.index))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The `n` elves carrying the most calories out of all those pushed, kept
+/// in a min-heap of at most `n` so that any number of elves can go by.
+pub struct Top {
+    n: usize,
+    heap: BinaryHeap<Reverse<Ranked>>,
+}
+
+impl Top {
+    pub fn new(n: usize) -> Self {
+        Top {
+            n,
+            heap: BinaryHeap::with_capacity(n + 1),
+        }
+    }
+
+    pub fn push(&mut self, elf: Elf) {
+        let elf = Ranked(elf);
+        if self.heap.len() < self.n {
+            self.heap.push(Reverse(elf));
+        } else if let Some(mut lowest) = self.heap.peek_mut() {
+            if elf > lowest.0 {
+                *lowest = Reverse(elf);
+            }
+        }
+    }
+
+    /// The elves kept, most calories first. There are fewer than `n` if
+    /// fewer than `n` were pushed.
+    pub fn into_ranked(self) -> Vec<Elf> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(Ranked(elf))| elf)
+            .collect()
+    }
+}
+
+pub fn top_elves(elves: impl IntoIterator<Item = Elf>, n: usize) -> Vec<Elf> {
+    let mut top = Top::new(n);
+    for elf in elves {
+        top.push(elf);
+    }
+    top.into_ranked()
+}
+
+fn top_total(elves: &[Elf], n: usize) -> u64 {
+    top_elves(elves.iter().copied(), n)
+        .iter()
+        .map(|elf| elf.calories)
+        .sum()
+}
+
+/// Inputs with at most this many elves are balanced exactly; larger ones
+/// only heuristically, since the exact search can take exponential time.
+pub const EXACT_LIMIT: usize = 16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Method {
+    Exact,
+    Greedy,
+    KarmarkarKarp,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Method::Exact => write!(f, "exact search"),
+            Method::Greedy => write!(f, "greedy"),
+            Method::KarmarkarKarp => write!(f, "Karmarkar-Karp"),
+        }
+    }
+}
+
+/// A split of the elves into teams, with the indices of each team's elves
+/// and the calories they carry between them, heaviest team first.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Teams {
+    pub method: Method,
+    /// Whether no other split has a lighter heaviest team: always so for
+    /// an exact search, and for a heuristic that reached the lower bound.
+    pub optimal: bool,
+    pub teams: Vec<(u64, Vec<usize>)>,
+}
+
+impl Teams {
+    fn new(method: Method, mut teams: Vec<(u64, Vec<usize>)>) -> Self {
+        for (_, elves) in teams.iter_mut() {
+            elves.sort_unstable();
+        }
+        teams.sort_by_key(|&(total, _)| Reverse(total));
+        Teams {
+            method,
+            optimal: method == Method::Exact,
+            teams,
+        }
+    }
+
+    pub fn heaviest(&self) -> u64 {
+        self.teams.first().map_or(0, |&(total, _)| total)
+    }
+
+    pub fn lightest(&self) -> u64 {
+        self.teams.last().map_or(0, |&(total, _)| total)
+    }
+
+    pub fn imbalance(&self) -> u64 {
+        self.heaviest() - self.lightest()
+    }
+}
+
+/// No split into `k` teams can have a heaviest team lighter than this.
+pub fn lower_bound(elves: &[Elf], k: usize) -> u64 {
+    let total: u64 = elves.iter().map(|e| e.calories).sum();
+    let largest = elves.iter().map(|e| e.calories).max().unwrap_or(0);
+    largest.max(total.div_ceil(k as u64))
+}
+
+/// Hands out the elves, heaviest first, each to the lightest team so far.
+fn greedy(elves: &[Elf], k: usize) -> Teams {
+    let mut sorted = elves.to_vec();
+    sorted.sort_by_key(|e| Reverse(e.calories));
+    let mut teams = vec![(0, Vec::new()); k];
+    for elf in sorted {
+        let lightest = teams.iter_mut().min_by_key(|(total, _)| *total).unwrap();
+        lightest.0 += elf.calories;
+        lightest.1.push(elf.index);
+    }
+    Teams::new(Method::Greedy, teams)
+}
+
+/// The Karmarkar-Karp differencing method for `k` teams: start with each
+/// elf as a split of its own, then keep merging the two splits whose teams
+/// differ the most, pairing the heavy teams of one with the light teams of
+/// the other, until only one is left.
+fn karmarkar_karp(elves: &[Elf], k: usize) -> Teams {
+    type Split = Vec<(u64, Vec<usize>)>;
+    let spread = |split: &Split| split[0].0 - split[k - 1].0;
+
+    let mut heap: BinaryHeap<(u64, usize, Split)> = BinaryHeap::new();
+    for (i, elf) in elves.iter().enumerate() {
+        let mut split = vec![(0, Vec::new()); k];
+        split[0] = (elf.calories, vec![elf.index]);
+        heap.push((spread(&split), i, split));
+    }
+    while heap.len() > 1 {
+        let (_, i, a) = heap.pop().unwrap();
+        let (_, _, b) = heap.pop().unwrap();
+        let mut merged: Split = a
+            .into_iter()
+            .zip(b.into_iter().rev())
+            .map(|((x, mut p), (y, q))| {
+                p.extend(q);
+                (x + y, p)
+            })
+            .collect();
+        merged.sort_by_key(|&(total, _)| Reverse(total));
+        heap.push((spread(&merged), i, merged));
+    }
+    let teams = heap
+        .pop()
+        .map_or_else(|| vec![(0, Vec::new()); k], |(_, _, split)| split);
+    Teams::new(Method::KarmarkarKarp, teams)
+}
+
+/// Branch and bound over every way to hand out the elves, heaviest first,
+/// looking only for splits better than `best`. Returns the best split found
+/// as each elf's team, if there is one.
+fn exact(elves: &[Elf], k: usize, best: u64) -> Option<Vec<usize>> {
+    struct Search<'a> {
+        weights: &'a [u64],
+        lower: u64,
+        loads: Vec<u64>,
+        assignment: Vec<usize>,
+        best: u64,
+        found: Option<Vec<usize>>,
+    }
+
+    impl Search<'_> {
+        fn go(&mut self, i: usize) {
+            if i == self.weights.len() {
+                self.best = self.loads.iter().copied().max().unwrap_or(0);
+                self.found = Some(self.assignment.clone());
+                return;
+            }
+            let weight = self.weights[i];
+            let mut tried: Vec<u64> = Vec::new();
+            for team in 0..self.loads.len() {
+                // Teams with the same load are interchangeable, empty ones
+                // included, so only try one of them.
+                let load = self.loads[team];
+                if load + weight >= self.best || tried.contains(&load) {
+                    continue;
+                }
+                tried.push(load);
+                self.loads[team] += weight;
+                self.assignment[i] = team;
+                self.go(i + 1);
+                self.loads[team] -= weight;
+                if self.best <= self.lower {
+                    return;
+                }
+            }
+        }
+    }
+
+    let weights: Vec<u64> = elves.iter().map(|e| e.calories).collect();
+    let mut search = Search {
+        weights: &weights,
+        lower: lower_bound(elves, k),
+        loads: vec![0; k],
+        assignment: vec![0; elves.len()],
+        best,
+        found: None,
+    };
+    search.go(0);
+    search.found
+}
+
+/// Splits the elves into `k` teams, keeping the heaviest team as light as
+/// possible: exactly for up to `EXACT_LIMIT` elves, and otherwise with
+/// whichever heuristic does better.
+pub fn balance(elves: &[Elf], k: usize) -> Teams {
+    assert!(k > 0, "cannot split elves into no teams");
+    let mut heuristic = [greedy(elves, k), karmarkar_karp(elves, k)]
+        .into_iter()
+        .min_by_key(|teams| (teams.heaviest(), teams.imbalance()))
+        .unwrap();
+    heuristic.optimal = heuristic.heaviest() == lower_bound(elves, k);
+    if heuristic.optimal || elves.len() > EXACT_LIMIT {
+        return heuristic;
+    }
+
+    let mut sorted = elves.to_vec();
+    sorted.sort_by_key(|e| Reverse(e.calories));
+    match exact(&sorted, k, heuristic.heaviest()) {
+        Some(assignment) => {
+            let mut teams = vec![(0, Vec::new()); k];
+            for (elf, team) in sorted.iter().zip(assignment) {
+                teams[team].0 += elf.calories;
+                teams[team].1.push(elf.index);
+            }
+            Teams::new(Method::Exact, teams)
+        }
+        // Nothing beats the heuristic, so it was optimal after all.
+        None => Teams {
+            optimal: true,
+            ..heuristic
+        },
+    }
+}
+
+/// What the elves carry of one kind of item between them.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ItemTotals {
+    /// How many lines list the item.
+    pub count: usize,
+    /// How many elves carry any of it.
+    pub elves: usize,
+    pub calories: u64,
+}
+
+/// Per-elf and per-item totals over a set of inventories, gathered one
+/// inventory at a time.
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+    pub elves: Vec<Elf>,
+    items: AHashMap<Option<String>, ItemTotals>,
+}
+
+impl Summary {
+    pub fn add(&mut self, inventory: &Inventory) {
+        self.elves.push(inventory.elf());
+        let mut seen = AHashSet::new();
+        for item in inventory.items.iter() {
+            let totals = self.items.entry(item.name.clone()).or_default();
+            totals.count += 1;
+            totals.calories += item.calories;
+            if seen.insert(&item.name) {
+                totals.elves += 1;
+            }
+        }
+    }
+
+    /// The totals for each item, by name, the most calories first. Bare
+    /// calorie counts come under `None`.
+    pub fn items(&self) -> Vec<(Option<&str>, &ItemTotals)> {
+        let mut items: Vec<_> = self
+            .items
+            .iter()
+            .map(|(name, totals)| (name.as_deref(), totals))
+            .collect();
+        items.sort_by_key(|&(name, totals)| (Reverse(totals.calories), name));
+        items
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        let total: f64 = self.elves.iter().map(|e| e.calories as f64).sum();
+        (!self.elves.is_empty()).then(|| total / self.elves.len() as f64)
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        let mut totals: Vec<u64> = self.elves.iter().map(|e| e.calories).collect();
+        totals.sort_unstable();
+        let mid = totals.len() / 2;
+        match totals.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(totals[mid] as f64),
+            _ => Some((totals[mid - 1] as f64 + totals[mid] as f64) / 2.0),
+        }
+    }
+
+    /// Counts the elves' totals into at most `bins` bins of equal width,
+    /// from the smallest total to the largest. Each bin includes its end,
+    /// so that a total of `u64::MAX` still has one.
+    pub fn histogram(&self, bins: usize) -> Vec<(RangeInclusive<u64>, usize)> {
+        let totals = self.elves.iter().map(|e| e.calories);
+        let (Some(min), Some(max)) = (totals.clone().min(), totals.clone().max()) else {
+            return Vec::new();
+        };
+        // Work in u128, since the span of `max - min + 1` totals, and so the
+        // width of a single bin, need not fit in a u64.
+        let span = (max - min) as u128 + 1;
+        let width = span.div_ceil(bins.max(1) as u128);
+        let count = span.div_ceil(width) as usize;
+        let mut histogram: Vec<(RangeInclusive<u64>, usize)> = (0..count as u128)
+            .map(|i| {
+                let start = min as u128 + i * width;
+                let end = (start + width - 1).min(u64::MAX as u128);
+                (start as u64..=end as u64, 0)
+            })
+            .collect();
+        for total in totals {
+            histogram[((total - min) as u128 / width) as usize].1 += 1;
+        }
+        histogram
+    }
+}
+
+fn format_stat(value: Option<f64>) -> String {
+    value.map_or_else(|| String::from("-"), |v| format!("{:.1}", v))
+}
+
+/// Writes the summary as tables for people, with the histogram drawn as
+/// bars.
+pub fn write_summary_text(out: &mut impl Write, summary: &Summary, bins: usize) -> io::Result<()> {
+    writeln!(out, "Elf  Items  Calories")?;
+    for elf in summary.elves.iter() {
+        writeln!(
+            out,
+            "{:>3}  {:>5}  {:>8}",
+            elf.index, elf.items, elf.calories
+        )?;
+    }
+
+    let items = summary.items();
+    let name_width = items
+        .iter()
+        .map(|(name, _)| name.map_or(1, |n| n.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .max("Item".len());
+    writeln!(out, "\n{:<name_width$}  Count  Elves  Calories", "Item")?;
+    for (name, totals) in items {
+        writeln!(
+            out,
+            "{:<name_width$}  {:>5}  {:>5}  {:>8}",
+            name.unwrap_or("-"),
+            totals.count,
+            totals.elves,
+            totals.calories
+        )?;
+    }
+
+    writeln!(
+        out,
+        "\nElves: {}, mean: {}, median: {}",
+        summary.elves.len(),
+        format_stat(summary.mean()),
+        format_stat(summary.median())
+    )?;
+    let histogram = summary.histogram(bins);
+    let most = histogram.iter().map(|&(_, n)| n).max().unwrap_or(0);
+    let labels: Vec<String> = histogram
+        .iter()
+        .map(|(range, _)| format!("{}..={}", range.start(), range.end()))
+        .collect();
+    let label_width = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+    for (label, (_, n)) in labels.iter().zip(histogram.iter()) {
+        // Scale the bars so that the longest is 40 characters.
+        let bar = "#".repeat((n * 40).div_ceil(most.max(1)));
+        let line = format!("{:>label_width$}  {:>4}  {}", label, n, bar);
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+/// Writes the summary as four CSV tables, each with a header and separated
+/// by a blank line: the elves, the items, the statistics and the histogram.
+pub fn write_summary_csv(out: &mut impl Write, summary: &Summary, bins: usize) -> io::Result<()> {
+    writeln!(out, "elf,items,calories")?;
+    for elf in summary.elves.iter() {
+        writeln!(out, "{},{},{}", elf.index, elf.items, elf.calories)?;
+    }
+
+    writeln!(out, "\nitem,count,elves,calories")?;
+    for (name, totals) in summary.items() {
+        writeln!(
+            out,
+            "{},{},{},{}",
+            report::csv_field(name.unwrap_or("")),
+            totals.count,
+            totals.elves,
+            totals.calories
+        )?;
+    }
+
+    writeln!(out, "\nstatistic,value")?;
+    writeln!(out, "elves,{}", summary.elves.len())?;
+    let stat = |v: Option<f64>| v.map_or_else(String::new, |v| v.to_string());
+    writeln!(out, "mean,{}", stat(summary.mean()))?;
+    writeln!(out, "median,{}", stat(summary.median()))?;
+
+    writeln!(out, "\nfirst,last,elves")?;
+    for (range, n) in summary.histogram(bins) {
+        writeln!(out, "{},{},{}", range.start(), range.end(), n)?;
+    }
+    Ok(())
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = Vec<Elf>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Elves::new(input.as_bytes())
+            .collect::<Result<_, _>>()
+            .map_err(|e| match e {
+                Error::Parse(e) => e,
+                // A string is already valid UTF-8 and in memory.
+                Error::Io(e) => unreachable!("reading a string failed: {}", e),
+            })
+    }
+
+    fn part_one(elves: &Self::Parsed) -> u64 {
+        top_total(elves, 1)
+    }
+
+    fn part_two(elves: &Self::Parsed) -> u64 {
+        top_total(elves, 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::generate::Rng;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day01.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day01::parse(EXAMPLE).unwrap();
+        assert_eq!(Day01::part_one(&parsed), 24000);
+        assert_eq!(Day01::part_two(&parsed), 45000);
+    }
+
+    #[test]
+    fn test_top() {
+        let elves = Day01::parse(EXAMPLE).unwrap();
+        let top = top_elves(elves.iter().copied(), 2);
+        assert_eq!(
+            top,
+            vec![
+                Elf {
+                    index: 3,
+                    calories: 24000,
+                    items: 3
+                },
+                Elf {
+                    index: 2,
+                    calories: 11000,
+                    items: 2
+                },
+            ]
+        );
+        assert_eq!(top_elves(elves.iter().copied(), 10).len(), 5);
+        assert!(top_elves(elves.iter().copied(), 0).is_empty());
+
+        // Equal elves rank in the order they came.
+        let ties = Day01::parse("5\n\n7\n\n5\n\n7\n").unwrap();
+        let order: Vec<usize> = top_elves(ties, 3).iter().map(|e| e.index).collect();
+        assert_eq!(order, vec![1, 3, 0]);
+    }
+
+    fn elves(calories: &[u64]) -> Vec<Elf> {
+        let elves = calories.iter().enumerate();
+        elves
+            .map(|(index, &calories)| Elf {
+                index,
+                calories,
+                items: 1,
+            })
+            .collect()
+    }
+
+    /// Tries every way to split the elves, returning the lightest heaviest
+    /// team.
+    fn brute_force(elves: &[Elf], k: usize) -> u64 {
+        let mut best = u64::MAX;
+        for mut code in 0..k.pow(elves.len() as u32) {
+            let mut loads = vec![0; k];
+            for elf in elves {
+                loads[code % k] += elf.calories;
+                code /= k;
+            }
+            best = best.min(*loads.iter().max().unwrap());
+        }
+        best
+    }
+
+    fn check_split(elves: &[Elf], k: usize, teams: &Teams) {
+        assert_eq!(teams.teams.len(), k);
+        let mut indices: Vec<usize> = teams.teams.iter().flat_map(|(_, t)| t.clone()).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..elves.len()).collect::<Vec<_>>());
+        for (total, team) in teams.teams.iter() {
+            assert_eq!(*total, team.iter().map(|&i| elves[i].calories).sum());
+        }
+    }
+
+    #[test]
+    fn test_balance() {
+        // Karmarkar-Karp leaves a difference of 2 here; 8+7 and 6+5+4 have
+        // none.
+        let example = elves(&[8, 7, 6, 5, 4]);
+        assert_eq!(karmarkar_karp(&example, 2).imbalance(), 2);
+        let teams = balance(&example, 2);
+        assert_eq!((teams.method, teams.imbalance()), (Method::Exact, 0));
+        assert!(teams.optimal);
+        check_split(&example, 2, &teams);
+
+        let mut rng = Rng::new(22);
+        for _ in 0..100 {
+            let n = rng.range(0, 8) as usize;
+            let k = rng.range(1, 4) as usize;
+            let calories: Vec<u64> = (0..n).map(|_| rng.range(1, 50) as u64).collect();
+            let elves = elves(&calories);
+            let teams = balance(&elves, k);
+            check_split(&elves, k, &teams);
+            let optimum = if n == 0 { 0 } else { brute_force(&elves, k) };
+            assert_eq!(teams.heaviest(), optimum, "{:?} into {}", calories, k);
+            for heuristic in [greedy(&elves, k), karmarkar_karp(&elves, k)] {
+                check_split(&elves, k, &heuristic);
+                assert!(heuristic.heaviest() >= optimum);
+            }
+        }
+
+        let many = elves(&(1..=100).collect::<Vec<_>>());
+        let teams = balance(&many, 3);
+        assert_ne!(teams.method, Method::Exact);
+        check_split(&many, 3, &teams);
+        assert_eq!(teams.heaviest(), lower_bound(&many, 3));
+        assert!(teams.optimal);
+    }
+
+    #[test]
+    fn test_items() {
+        let item = |line| parse_item(line, 1);
+        let named = |name: &str, calories| {
+            Ok(Item {
+                name: Some(String::from(name)),
+                calories,
+            })
+        };
+        assert_eq!(
+            item("300"),
+            Ok(Item {
+                name: None,
+                calories: 300
+            })
+        );
+        assert_eq!(item("apple 300"), named("apple", 300));
+        assert_eq!(item("ration:1200"), named("ration", 1200));
+        assert_eq!(item("  dried fig : 7"), named("dried fig", 7));
+        let error = |column, expected| Err(ParseError::new(1, column, expected));
+        assert_eq!(
+            item("apple300"),
+            error(6, "\":\" or a space before the count")
+        );
+        assert_eq!(item(": 300"), error(1, "an item name"));
+        assert_eq!(item("apple"), error(1, "a calorie count or a blank line"));
+        assert_eq!(
+            item("x 99999999999999999999"),
+            error(3, "a calorie count in range")
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let input = "apple 300\nration:1200\n\nfig: 50\napple 100\napple 20\n\n\n400\n";
+        let mut summary = Summary::default();
+        for inventory in Inventories::new(input.as_bytes()) {
+            summary.add(&inventory.unwrap());
+        }
+        let totals: Vec<u64> = summary.elves.iter().map(|e| e.calories).collect();
+        assert_eq!(totals, vec![1500, 170, 400]);
+        assert_eq!(
+            summary.items()[..2],
+            [
+                (
+                    Some("ration"),
+                    &ItemTotals {
+                        count: 1,
+                        elves: 1,
+                        calories: 1200
+                    }
+                ),
+                (
+                    Some("apple"),
+                    &ItemTotals {
+                        count: 3,
+                        elves: 2,
+                        calories: 420
+                    }
+                ),
+            ]
+        );
+        assert_eq!(summary.items()[2].0, None);
+        assert_eq!(
+            (summary.mean(), summary.median()),
+            (Some(690.0), Some(400.0))
+        );
+        assert_eq!(summary.histogram(2), vec![(170..=835, 2), (836..=1501, 1)]);
+        assert_eq!(summary.histogram(10000).len(), 1331);
+
+        let mut csv = Vec::new();
+        write_summary_csv(&mut csv, &summary, 2).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("elf,items,calories\n0,2,1500\n"));
+        assert!(csv.contains("\nstatistic,value\nelves,3\nmean,690\nmedian,400\n"));
+        assert!(csv.ends_with("\nfirst,last,elves\n170,835,2\n836,1501,1\n"));
+
+        let mut extremes = Summary::default();
+        for inventory in Inventories::new("0\n\n18446744073709551615\n".as_bytes()) {
+            extremes.add(&inventory.unwrap());
+        }
+        assert_eq!(extremes.histogram(1), vec![(0..=u64::MAX, 2)]);
+        assert_eq!(
+            extremes.histogram(2),
+            vec![(0..=u64::MAX / 2, 1), (u64::MAX / 2 + 1..=u64::MAX, 1)]
+        );
+        assert_eq!(extremes.mean(), Some(u64::MAX as f64 / 2.0));
+
+        let empty = Summary::default();
+        assert_eq!((empty.mean(), empty.median()), (None, None));
+        assert!(empty.histogram(5).is_empty());
+    }
+
+    #[test]
+    fn test_short_inputs() {
+        for (input, one, two) in [
+            ("", 0, 0),
+            ("\n\n", 0, 0),
+            ("5\n", 5, 5),
+            ("1\n\n\n2\n", 2, 3),
+        ] {
+            let elves = Day01::parse(input).unwrap();
+            assert_eq!(Day01::part_one(&elves), one);
+            assert_eq!(Day01::part_two(&elves), two);
+        }
+        assert_eq!(
+            Day01::parse("1\n2\n\n3x\n").unwrap_err(),
+            ParseError::new(4, 2, "a calorie count or a blank line")
+        );
+    }
+
+    #[test]
+    fn test_totals_in_range() {
+        let near = u64::MAX - 1;
+        let elves = Day01::parse(&format!("{}\n1\n", near)).unwrap();
+        assert_eq!(Day01::part_two(&elves), u64::MAX);
+
+        let expected = "a calorie count keeping the total of all elves in range";
+        // Within one elf, and across elves, as the top three add up.
+        assert_eq!(
+            Day01::parse(&format!("{}\nfig: 2\n", near)).unwrap_err(),
+            ParseError::new(2, 6, expected)
+        );
+        assert_eq!(
+            Day01::parse(&format!("{}\n\n{}\n", near, near)).unwrap_err(),
+            ParseError::new(3, 1, expected)
+        );
+    }
+}
@@ -0,0 +1,558 @@
+use nom::character::complete::satisfy;
+
+use crate::error::ParseError;
+use crate::parsers::{finish, lines, tag, IResult, NomError};
+use crate::Solution;
+
+/// The rules of Rock Paper Scissors, as the puzzle plays it.
+const STANDARD_RULES: &str = include_str!("../data/rules/rps.txt");
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Loss => "loss",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Shape {
+    pub name: String,
+    /// What the opponent's column calls it.
+    pub opponent_code: char,
+    /// What your column calls it, when the column stands for shapes.
+    pub code: char,
+    pub score: u64,
+}
+
+/// The rules of a game like Rock Paper Scissors with any number of shapes:
+/// what beats what, and how each round scores.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rules {
+    pub shapes: Vec<Shape>,
+    /// `beats[a][b]` is whether shape `a` beats shape `b`.
+    beats: Vec<Vec<bool>>,
+    /// The code and score of each outcome, in the order of `Outcome::ALL`.
+    outcomes: [(char, u64); 3],
+}
+
+/// One line of a rules file, split into words that still point into it.
+fn words(line: &str) -> Vec<&str> {
+    line.split_whitespace().collect()
+}
+
+impl Rules {
+    /// Reads a rules file: `shape`, `beats` and `outcome` lines, as in
+    /// `data/rules/rps.txt`, with `#` starting a comment. The "beats"
+    /// relation must be a tournament: between every two different shapes,
+    /// exactly one beats the other.
+    pub fn parse(config: &str) -> Result<Self, ParseError> {
+        let error = |at: &str, expected: String| ParseError::at(config, at, expected);
+        let code = |word: &str| {
+            let mut chars = word.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => Err(error(word, String::from("a one character code"))),
+            }
+        };
+        let score = |word: &str| {
+            word.parse::<u64>()
+                .map_err(|_| error(word, String::from("a score")))
+        };
+
+        let mut shapes: Vec<Shape> = Vec::new();
+        let mut beats: Vec<(usize, usize)> = Vec::new();
+        let mut outcomes = [None; 3];
+        for line in config.lines() {
+            let line = line.split('#').next().unwrap();
+            match words(line)[..] {
+                [] => (),
+                ["shape", name, opponent_code, own_code, points] => {
+                    if shapes.iter().any(|s| s.name == name) {
+                        return Err(error(name, String::from("a new shape name")));
+                    }
+                    let (opponent_code, own_code) = (code(opponent_code)?, code(own_code)?);
+                    if shapes.iter().any(|s| s.opponent_code == opponent_code) {
+                        return Err(error(
+                            words(line)[2],
+                            String::from("an opponent's code not used by another shape"),
+                        ));
+                    }
+                    if shapes.iter().any(|s| s.code == own_code) {
+                        return Err(error(
+                            words(line)[3],
+                            String::from("a code not used by another shape"),
+                        ));
+                    }
+                    shapes.push(Shape {
+                        name: String::from(name),
+                        opponent_code,
+                        code: own_code,
+                        score: score(points)?,
+                    });
+                }
+                ["outcome", name, outcome_code, points] => {
+                    let i = Outcome::ALL
+                        .iter()
+                        .position(|o| o.name() == name)
+                        .ok_or_else(|| error(name, String::from("loss, draw or win")))?;
+                    let outcome_code = code(outcome_code)?;
+                    if outcomes.iter().flatten().any(|&(c, _)| c == outcome_code) {
+                        return Err(error(
+                            words(line)[2],
+                            String::from("a code not used by another outcome"),
+                        ));
+                    }
+                    if outcomes[i].is_some() {
+                        return Err(error(name, String::from("an outcome not already given")));
+                    }
+                    outcomes[i] = Some((outcome_code, score(points)?));
+                }
+                [winner, "beats", ref losers @ ..] if !losers.is_empty() => {
+                    let find = |name: &str| {
+                        shapes
+                            .iter()
+                            .position(|s| s.name == name)
+                            .ok_or_else(|| error(name, String::from("a shape defined above")))
+                    };
+                    let a = find(winner)?;
+                    for &loser in losers {
+                        let b = find(loser)?;
+                        if a == b {
+                            let expected = format!("a shape other than {}", winner);
+                            return Err(error(loser, expected));
+                        }
+                        if beats.contains(&(a, b)) || beats.contains(&(b, a)) {
+                            let expected = format!("a shape not already paired with {}", winner);
+                            return Err(error(loser, expected));
+                        }
+                        beats.push((a, b));
+                    }
+                }
+                _ => {
+                    let first = words(line)[0];
+                    let expected = "a shape, outcome or \"beats\" line";
+                    return Err(error(first, String::from(expected)));
+                }
+            }
+        }
+
+        let end = &config[config.len()..];
+        if shapes.len() < 2 {
+            return Err(error(end, String::from("at least two shapes")));
+        }
+        let mut table = vec![vec![false; shapes.len()]; shapes.len()];
+        for &(a, b) in beats.iter() {
+            table[a][b] = true;
+        }
+        for a in 0..shapes.len() {
+            for b in a + 1..shapes.len() {
+                if !table[a][b] && !table[b][a] {
+                    let expected =
+                        format!("a rule for {} against {}", shapes[a].name, shapes[b].name);
+                    return Err(error(end, expected));
+                }
+            }
+        }
+        let mut defined = [(' ', 0); 3];
+        for (i, outcome) in Outcome::ALL.iter().enumerate() {
+            defined[i] = outcomes[i]
+                .ok_or_else(|| error(end, format!("an outcome line for a {}", outcome.name())))?;
+        }
+        Ok(Rules {
+            shapes,
+            beats: table,
+            outcomes: defined,
+        })
+    }
+
+    pub fn standard() -> Self {
+        Rules::parse(STANDARD_RULES).expect("the standard rules are valid")
+    }
+
+    pub fn outcome(&self, shape: usize, against: usize) -> Outcome {
+        if shape == against {
+            Outcome::Draw
+        } else if self.beats[shape][against] {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    /// The score for playing `shape` against `against`.
+    pub fn score(&self, shape: usize, against: usize) -> u64 {
+        let outcome = self.outcome(shape, against);
+        self.shapes[shape].score + self.outcomes[outcome as usize].1
+    }
+
+    /// The first shape, in the order the rules list them, that gets
+    /// `outcome` against `against`, if any does.
+    pub fn shape_for(&self, outcome: Outcome, against: usize) -> Option<usize> {
+        (0..self.shapes.len()).find(|&shape| self.outcome(shape, against) == outcome)
+    }
+
+    /// The shape codes as the rules define them.
+    pub fn shape_mapping(&self) -> Mapping {
+        Mapping::Shapes(self.shapes.iter().map(|s| s.code).zip(0..).collect())
+    }
+
+    /// The outcome codes as the rules define them.
+    pub fn outcome_mapping(&self) -> Mapping {
+        let codes = self.outcomes.iter().map(|&(c, _)| c);
+        Mapping::Outcomes(codes.zip(Outcome::ALL).collect())
+    }
+
+    /// Lists `codes` as the alternatives a parse error expects.
+    fn describe(codes: impl Iterator<Item = char>) -> String {
+        let codes: Vec<String> = codes.map(|c| format!("{:?}", c.to_string())).collect();
+        codes.join(" or ")
+    }
+}
+
+/// One line of the strategy guide: the opponent's shape, and the code in
+/// the second column, whose meaning differs per part.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Round {
+    pub opponent: usize,
+    pub code: char,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Guide {
+    pub rules: Rules,
+    pub rounds: Vec<Round>,
+}
+
+/// Parses one of `codes`, or fails expecting `expected`.
+fn code_in<'a>(codes: &[char], expected: &str, input: &'a str) -> IResult<'a, char> {
+    satisfy(|c| codes.contains(&c))(input)
+        .map_err(|_: nom::Err<NomError>| nom::Err::Error(NomError::new(input, expected)))
+}
+
+impl Guide {
+    /// Reads a strategy guide for a game played by `rules`.
+    pub fn parse(input: &str, rules: Rules) -> Result<Self, ParseError> {
+        let opponents = Rules::describe(rules.shapes.iter().map(|s| s.opponent_code));
+        let mut codes: Vec<char> = rules.shapes.iter().map(|s| s.code).collect();
+        codes.extend(rules.outcomes.iter().map(|&(c, _)| c));
+        codes.sort_unstable();
+        codes.dedup();
+        let own = Rules::describe(codes.iter().copied());
+
+        let opponent_codes: Vec<char> = rules.shapes.iter().map(|s| s.opponent_code).collect();
+        let round = |input| {
+            let (rest, opponent) = code_in(&opponent_codes, &opponents, input)?;
+            let (rest, _) = tag(" ")(rest)?;
+            let (rest, code) = code_in(&codes, &own, rest)?;
+            let opponent = rules
+                .shapes
+                .iter()
+                .position(|s| s.opponent_code == opponent)
+                .unwrap();
+            Ok((rest, Round { opponent, code }))
+        };
+        let rounds = finish(input, lines(round))?;
+        Ok(Guide { rules, rounds })
+    }
+
+    /// The total score if the second column stands for shapes, as the
+    /// rules define their codes.
+    pub fn score_by_shape(&self) -> Result<u64, String> {
+        self.score_with(&self.rules.shape_mapping())
+    }
+
+    /// The total score if the second column stands for outcomes, as the
+    /// rules define their codes.
+    pub fn score_by_outcome(&self) -> Result<u64, String> {
+        self.score_with(&self.rules.outcome_mapping())
+    }
+
+    /// The total score if the second column means what `mapping` says.
+    pub fn score_with(&self, mapping: &Mapping) -> Result<u64, String> {
+        let rules = &self.rules;
+        self.rounds
+            .iter()
+            .map(|round| {
+                let shape = match mapping {
+                    Mapping::Shapes(codes) => lookup(codes, round.code, "a shape")?,
+                    Mapping::Outcomes(codes) => {
+                        let outcome = lookup(codes, round.code, "an outcome")?;
+                        rules.shape_for(outcome, round.opponent).ok_or_else(|| {
+                            format!(
+                                "no shape gets a {} against {}",
+                                outcome.name(),
+                                rules.shapes[round.opponent].name
+                            )
+                        })?
+                    }
+                };
+                Ok(rules.score(shape, round.opponent))
+            })
+            .sum()
+    }
+
+    /// Scores the guide under every way of reading the second column: as
+    /// each assignment of the shape codes to shapes, and of the outcome
+    /// codes to outcomes. There are n! of the first for n shapes, so this
+    /// is only practical for a handful of them.
+    pub fn decode(&self) -> Decoding {
+        let rules = &self.rules;
+        let shape_codes: Vec<char> = rules.shapes.iter().map(|s| s.code).collect();
+        let outcome_codes: Vec<char> = rules.outcomes.iter().map(|&(c, _)| c).collect();
+        let shapes = permutations(shape_codes.len())
+            .into_iter()
+            .map(|order| Mapping::Shapes(shape_codes.iter().copied().zip(order).collect()));
+        let outcomes = permutations(outcome_codes.len()).into_iter().map(|order| {
+            let outcomes = order.into_iter().map(|i| Outcome::ALL[i]);
+            Mapping::Outcomes(outcome_codes.iter().copied().zip(outcomes).collect())
+        });
+        let readings = shapes
+            .chain(outcomes)
+            .map(|mapping| {
+                let score = self.score_with(&mapping);
+                (mapping, score)
+            })
+            .collect();
+        Decoding { readings }
+    }
+}
+
+fn lookup<T: Copy>(codes: &[(char, T)], code: char, what: &str) -> Result<T, String> {
+    codes
+        .iter()
+        .find(|&&(c, _)| c == code)
+        .map(|&(_, t)| t)
+        .ok_or_else(|| format!("{:?} does not stand for {}", code, what))
+}
+
+/// Steps `order` to the next permutation in lexicographic order, returning
+/// false once it was the last.
+fn next_permutation(order: &mut [usize]) -> bool {
+    let Some(i) = (1..order.len()).rev().find(|&i| order[i - 1] < order[i]) else {
+        return false;
+    };
+    let j = (i..order.len())
+        .rev()
+        .find(|&j| order[j] > order[i - 1])
+        .unwrap();
+    order.swap(i - 1, j);
+    order[i..].reverse();
+    true
+}
+
+/// Every ordering of `0..n`, starting with the identity.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut all = vec![order.clone()];
+    while next_permutation(&mut order) {
+        all.push(order.clone());
+    }
+    all
+}
+
+/// What the codes of the guide's second column stand for.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Mapping {
+    /// Each code is the shape to play.
+    Shapes(Vec<(char, usize)>),
+    /// Each code is how the round must end.
+    Outcomes(Vec<(char, Outcome)>),
+}
+
+impl Mapping {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Mapping::Shapes(_) => "shapes",
+            Mapping::Outcomes(_) => "outcomes",
+        }
+    }
+
+    /// The mapping as `X=rock Y=paper Z=scissors`.
+    pub fn describe(&self, rules: &Rules) -> String {
+        let pairs: Vec<String> = match self {
+            Mapping::Shapes(codes) => codes
+                .iter()
+                .map(|&(c, shape)| format!("{}={}", c, rules.shapes[shape].name))
+                .collect(),
+            Mapping::Outcomes(codes) => codes
+                .iter()
+                .map(|&(c, outcome)| format!("{}={}", c, outcome.name()))
+                .collect(),
+        };
+        pairs.join(" ")
+    }
+}
+
+/// The guide scored under every mapping `Guide::decode` tried, in the order
+/// it tried them. A mapping the guide cannot be followed under has an error
+/// instead of a score.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Decoding {
+    pub readings: Vec<(Mapping, Result<u64, String>)>,
+}
+
+impl Decoding {
+    fn scores(&self) -> impl Iterator<Item = u64> + '_ {
+        self.readings
+            .iter()
+            .filter_map(|(_, score)| score.clone().ok())
+    }
+
+    pub fn best(&self) -> Option<u64> {
+        self.scores().max()
+    }
+
+    pub fn worst(&self) -> Option<u64> {
+        self.scores().min()
+    }
+
+    /// The mappings under which the guide scores exactly `score`.
+    pub fn matching(&self, score: u64) -> Vec<&Mapping> {
+        self.readings
+            .iter()
+            .filter(|(_, s)| *s == Ok(score))
+            .map(|(mapping, _)| mapping)
+            .collect()
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Guide;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Guide::parse(input, Rules::standard())
+    }
+
+    // Every code of the standard rules stands for both a shape and an
+    // outcome, and every outcome can be had against every shape.
+    fn part_one(guide: &Self::Parsed) -> u64 {
+        guide.score_by_shape().unwrap()
+    }
+
+    fn part_two(guide: &Self::Parsed) -> u64 {
+        guide.score_by_outcome().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day02.txt");
+    const RPSLS: &str = include_str!("../data/rules/rpsls.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day02::parse(EXAMPLE).unwrap();
+        assert_eq!(Day02::part_one(&parsed), 15);
+        assert_eq!(Day02::part_two(&parsed), 12);
+    }
+
+    #[test]
+    fn test_rpsls() {
+        let rules = Rules::parse(RPSLS).unwrap();
+        let shape = |name| rules.shapes.iter().position(|s| s.name == name).unwrap();
+        let (rock, spock) = (shape("rock"), shape("spock"));
+        assert_eq!(rules.outcome(spock, rock), Outcome::Win);
+        assert_eq!(rules.outcome(rock, spock), Outcome::Loss);
+        assert_eq!(rules.score(spock, rock), 11);
+        // Every shape beats exactly two of the others.
+        for a in 0..5 {
+            let wins = (0..5).filter(|&b| rules.outcome(a, b) == Outcome::Win);
+            assert_eq!(wins.count(), 2);
+        }
+
+        // Paper and spock both beat rock, and paper is listed first; rock
+        // is the first to lose to spock.
+        let guide = Guide::parse("A Z\nE X\n", rules).unwrap();
+        assert_eq!(guide.score_by_outcome(), Ok(2 + 6 + 1));
+        assert_eq!(guide.score_by_shape(), Ok(5 + 6 + 3));
+        assert_eq!(
+            Guide::parse("F X\n", Rules::parse(RPSLS).unwrap()).unwrap_err(),
+            ParseError::new(1, 1, "\"A\" or \"B\" or \"C\" or \"D\" or \"E\"")
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let guide = Day02::parse(EXAMPLE).unwrap();
+        let decoding = guide.decode();
+        assert_eq!(decoding.readings.len(), 12);
+        assert_eq!(decoding.readings[0], (guide.rules.shape_mapping(), Ok(15)));
+        assert_eq!(
+            decoding.readings[6],
+            (guide.rules.outcome_mapping(), Ok(12))
+        );
+
+        // Each code comes up once, so the best is winning every round.
+        let best = decoding.matching(decoding.best().unwrap());
+        assert_eq!(decoding.best(), Some(24));
+        assert_eq!(best.len(), 1);
+        assert_eq!(best[0].describe(&guide.rules), "X=scissors Y=paper Z=rock");
+        assert_eq!(decoding.worst(), Some(6));
+        assert!(decoding.matching(7).is_empty());
+
+        let mut order = [0, 2, 1];
+        assert!(next_permutation(&mut order));
+        assert_eq!(order, [1, 0, 2]);
+        assert!(!next_permutation(&mut [2, 1, 0]));
+        assert_eq!(permutations(4).len(), 24);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let error = |config: &str| Rules::parse(config).unwrap_err();
+        let shapes = "shape rock A X 1\nshape paper B Y 2\n";
+        let outcomes = "outcome loss X 0\noutcome draw Y 3\noutcome win Z 6\n";
+        let valid = format!("{}paper beats rock\n{}", shapes, outcomes);
+        assert!(Rules::parse(&valid).is_ok());
+
+        let missing = format!("{}{}", shapes, outcomes);
+        assert_eq!(error(&missing).expected, "a rule for rock against paper");
+        let itself = format!("{}rock beats rock\n{}", shapes, outcomes);
+        assert_eq!(
+            error(&itself),
+            ParseError::new(3, 12, "a shape other than rock")
+        );
+        let both = format!("{}paper beats rock\nrock beats paper\n{}", shapes, outcomes);
+        assert_eq!(
+            error(&both),
+            ParseError::new(4, 12, "a shape not already paired with rock")
+        );
+        let unknown = format!("{}paper beats stone\n{}", shapes, outcomes);
+        assert_eq!(
+            error(&unknown),
+            ParseError::new(3, 13, "a shape defined above")
+        );
+        let no_win = format!("{}paper beats rock\noutcome loss X 0\n", shapes);
+        assert_eq!(error(&no_win).expected, "an outcome line for a draw");
+        assert_eq!(
+            error("shape rock A X 1\nshape stone A Z 1\n"),
+            ParseError::new(2, 13, "an opponent's code not used by another shape")
+        );
+        assert_eq!(
+            error("shape rock AA X 1\n"),
+            ParseError::new(1, 12, "a one character code")
+        );
+        assert_eq!(
+            error("rock wins\n"),
+            ParseError::new(1, 1, "a shape, outcome or \"beats\" line")
+        );
+    }
+}
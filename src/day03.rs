@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use crate::error::ParseError;
+use crate::Solution;
+
+fn prio(c: char) -> u64 {
+    if c.is_ascii_lowercase() {
+        (c as u8 - b'a' + 1) as u64
+    } else {
+        (c as u8 - b'A' + 27) as u64
+    }
+}
+
+fn find_common_char<S: AsRef<str>>(v: &[S]) -> Option<char> {
+    let (first, rest) = v.split_first()?;
+    let mut common: HashSet<char> = first.as_ref().chars().collect();
+    for s in rest.iter() {
+        let t: HashSet<char> = s.as_ref().chars().collect();
+        common = common.intersection(&t).copied().collect();
+    }
+    common.iter().next().copied()
+}
+
+fn common_char(v: &[String]) -> char {
+    find_common_char(v).expect("parsing checked for a common item")
+}
+
+fn lines_to_vecs_p1(lines: &[String]) -> Vec<Vec<String>> {
+    let mut result = Vec::new();
+    for line in lines.iter() {
+        let half_length = line.len() / 2;
+        let a = String::from(&line[..half_length]);
+        let b = String::from(&line[half_length..]);
+        result.push(vec![a, b])
+    }
+    result
+}
+
+fn lines_to_vecs_p2(lines: &[String]) -> Vec<Vec<String>> {
+    lines.chunks(3).map(Vec::from).collect()
+}
+
+fn common_prio(v: &[String], f: fn(&[String]) -> Vec<Vec<String>>) -> u64 {
+    f(v).iter().map(|v| common_char(v)).map(prio).sum()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Vec<String>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut lines = Vec::new();
+        let mut group = Vec::new();
+        for line in input.lines() {
+            if line.is_empty() {
+                return Err(ParseError::at(input, line, "a rucksack"));
+            }
+            if let Some(bad) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+                return Err(ParseError::at(input, &line[bad..], "an item letter"));
+            }
+            let (a, b) = line.split_at(line.len() / 2);
+            if line.len() % 2 == 1 {
+                let expected = "a rucksack with as many items in each compartment";
+                return Err(ParseError::at(input, line, expected));
+            }
+            if find_common_char(&[a, b]).is_none() {
+                let expected = "a rucksack with an item in both compartments";
+                return Err(ParseError::at(input, line, expected));
+            }
+            group.push(line);
+            if group.len() == 3 {
+                if find_common_char(&group).is_none() {
+                    let expected = "a rucksack sharing a badge with the two before it";
+                    return Err(ParseError::at(input, line, expected));
+                }
+                group.clear();
+            }
+            lines.push(String::from(line));
+        }
+        if !group.is_empty() {
+            let end = &input[input.len()..];
+            return Err(ParseError::at(input, end, "a group of three rucksacks"));
+        }
+        Ok(lines)
+    }
+
+    fn part_one(lines: &Self::Parsed) -> u64 {
+        common_prio(lines, lines_to_vecs_p1)
+    }
+
+    fn part_two(lines: &Self::Parsed) -> u64 {
+        common_prio(lines, lines_to_vecs_p2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day03.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(Day03::part_one(&parsed), 157);
+        assert_eq!(Day03::part_two(&parsed), 70);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let error = |input| Day03::parse(input).unwrap_err();
+        assert_eq!(error("abca\n\nefge\n"), ParseError::new(2, 1, "a rucksack"));
+        assert_eq!(
+            error("abc\n"),
+            ParseError::new(1, 1, "a rucksack with as many items in each compartment")
+        );
+        assert_eq!(
+            error("abcd\n"),
+            ParseError::new(1, 1, "a rucksack with an item in both compartments")
+        );
+        assert_eq!(
+            error("abca\nabcb\nzzzz\n"),
+            ParseError::new(3, 1, "a rucksack sharing a badge with the two before it")
+        );
+        assert_eq!(
+            error("abca\nabcb\n"),
+            ParseError::new(3, 1, "a group of three rucksacks")
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use nom::combinator::map;
+use nom::sequence::separated_pair;
+
+use crate::error::ParseError;
+use crate::interval::IntervalSet;
+use crate::parsers::{finish, lines, tag, unsigned, IResult, NomError};
+use crate::Solution;
+
+type Sections = IntervalSet<u32>;
+
+#[derive(Debug)]
+pub struct RangePair {
+    a: Sections,
+    b: Sections,
+}
+
+impl RangePair {
+    fn is_fully_contained(&self) -> bool {
+        self.a.is_subset(&self.b) || self.b.is_subset(&self.a)
+    }
+    fn overlaps(&self) -> bool {
+        !self.a.is_disjoint(&self.b)
+    }
+}
+
+fn parse_range(input: &str) -> IResult<'_, Sections> {
+    let (rest, (start, end)) = separated_pair(unsigned, tag("-"), unsigned)(input)?;
+    let sections = Sections::try_from(start..=end).map_err(|_| {
+        let end_at = &input[input.find('-').unwrap() + 1..];
+        let expected = format!("a section below {}", u32::MAX);
+        nom::Err::Error(NomError::new(end_at, expected))
+    })?;
+    Ok((rest, sections))
+}
+
+fn parse_range_pair_raw(input: &str) -> IResult<'_, (Sections, Sections)> {
+    separated_pair(parse_range, tag(","), parse_range)(input)
+}
+
+fn parse_range_pair(input: &str) -> IResult<'_, RangePair> {
+    let make_range = |(a, b)| RangePair { a, b };
+    map(parse_range_pair_raw, make_range)(input)
+}
+
+fn parse_range_pairs(input: &str) -> IResult<'_, Vec<RangePair>> {
+    lines(parse_range_pair)(input)
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = Vec<RangePair>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        finish(input, parse_range_pairs)
+    }
+
+    fn part_one(pairs: &Self::Parsed) -> usize {
+        pairs.iter().filter(|&p| p.is_fully_contained()).count()
+    }
+
+    fn part_two(pairs: &Self::Parsed) -> usize {
+        pairs.iter().filter(|&p| p.overlaps()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    use crate::generate::{self, Rng};
+
+    const EXAMPLE: &str = include_str!("../data/examples/day04.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part_one(&parsed), 2);
+        assert_eq!(Day04::part_two(&parsed), 4);
+    }
+
+    #[test]
+    fn test_largest_section() {
+        let parsed = Day04::parse("1-4294967294,2-3\n").unwrap();
+        assert_eq!(Day04::part_two(&parsed), 1);
+        assert_eq!(
+            Day04::parse("1-4294967295,2-3\n").err(),
+            Some(ParseError::new(1, 3, "a section below 4294967295"))
+        );
+    }
+
+    #[test]
+    fn test_random_inputs() {
+        for seed in 0..20 {
+            let input = generate::day04(&mut Rng::new(seed), 50);
+            let pairs = Day04::parse(&input).unwrap();
+            for p in pairs.iter() {
+                let sections = |s: &Sections| s.iter().flatten().collect::<HashSet<u32>>();
+                let (a, b) = (sections(&p.a), sections(&p.b));
+                assert_eq!(p.is_fully_contained(), a.is_subset(&b) || b.is_subset(&a));
+                assert_eq!(p.overlaps(), !a.is_disjoint(&b));
+            }
+            assert!(Day04::part_one(&pairs) <= Day04::part_two(&pairs));
+        }
+    }
+}
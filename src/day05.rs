@@ -0,0 +1,189 @@
+use nom::sequence::tuple;
+
+use crate::error::ParseError;
+use crate::parsers::{complete, lines, tag, unsigned, IResult, NomError};
+use crate::Solution;
+
+type Crate = char;
+type Stack = Vec<Crate>;
+
+#[derive(Debug)]
+pub struct Move {
+    quantity: u32,
+    from: usize,
+    to: usize,
+}
+
+impl Move {
+    fn apply_p1(&self, mut stacks: Vec<Stack>) -> Vec<Stack> {
+        let split_index = stacks[self.from].len() - (self.quantity as usize);
+        let tail = stacks[self.from].split_off(split_index);
+        let mut tail = tail.iter().rev().copied().collect();
+        stacks[self.to].append(&mut tail);
+        stacks
+    }
+
+    fn apply_p2(&self, mut stacks: Vec<Stack>) -> Vec<Stack> {
+        let split_index = stacks[self.from].len() - (self.quantity as usize);
+        let mut tail = stacks[self.from].split_off(split_index);
+        stacks[self.to].append(&mut tail);
+        stacks
+    }
+}
+
+fn parse_stack_index(num_stacks: usize) -> impl Fn(&str) -> IResult<'_, usize> {
+    move |input: &str| {
+        let (rest, n) = unsigned::<usize>(input)?;
+        if n == 0 || n > num_stacks {
+            let expected = format!("a stack number from 1 to {}", num_stacks);
+            return Err(nom::Err::Error(NomError::new(input, expected)));
+        }
+        Ok((rest, n - 1))
+    }
+}
+
+fn parse_move(num_stacks: usize) -> impl Fn(&str) -> IResult<'_, Move> {
+    move |input: &str| {
+        let (rest, (_, quantity, _, from, _, to)) = tuple((
+            tag("move "),
+            unsigned,
+            tag(" from "),
+            parse_stack_index(num_stacks),
+            tag(" to "),
+            parse_stack_index(num_stacks),
+        ))(input)?;
+        Ok((rest, Move { quantity, from, to }))
+    }
+}
+
+fn parse_moves(input: &str, num_stacks: usize) -> IResult<'_, Vec<Move>> {
+    lines(parse_move(num_stacks))(input)
+}
+
+fn get_stacks(input: &str, drawing: &str) -> Result<Vec<Stack>, ParseError> {
+    let lines: Vec<&str> = drawing.lines().rev().filter(|s| !s.is_empty()).collect();
+    let labels = lines
+        .first()
+        .ok_or_else(|| ParseError::at(input, drawing, "a drawing of the stacks"))?;
+    let num_stacks = (labels.len() + 1) / 4;
+    let mut stacks = vec![Vec::new(); num_stacks];
+    for s in lines[1..].iter() {
+        let chars: Vec<char> = s.chars().collect();
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            match chars.get(i * 4 + 1) {
+                Some(' ') | None => (),
+                Some(&c) => stack.push(c),
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+/// Follows how tall each stack gets, to reject a move of more crates than
+/// its stack holds, or moves that leave a stack with no crate on top.
+fn check_heights(
+    input: &str,
+    raw_moves: &str,
+    stacks: &[Stack],
+    moves: &[Move],
+) -> Result<(), ParseError> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for (m, line) in moves.iter().zip(raw_moves.lines()) {
+        let quantity = m.quantity as usize;
+        if quantity > heights[m.from] {
+            let expected = format!("a quantity of at most {}", heights[m.from]);
+            return Err(ParseError::at(input, &line["move ".len()..], expected));
+        }
+        heights[m.from] -= quantity;
+        heights[m.to] += quantity;
+    }
+    if let Some(empty) = heights.iter().position(|&h| h == 0) {
+        let end = &input[input.len()..];
+        let expected = format!("moves that leave a crate on stack {}", empty + 1);
+        return Err(ParseError::at(input, end, expected));
+    }
+    Ok(())
+}
+
+fn top_crates(stacks: &[Stack]) -> String {
+    stacks
+        .iter()
+        .map(|stack| {
+            stack
+                .last()
+                .expect("parsing checked every stack ends with a crate")
+        })
+        .collect()
+}
+
+pub struct Supplies {
+    stacks: Vec<Stack>,
+    moves: Vec<Move>,
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = Supplies;
+    type PartOne = String;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let end = &input[input.len()..];
+        let split = input
+            .find("move")
+            .ok_or_else(|| ParseError::at(input, end, "a \"move\" line"))?;
+        let (raw_stacks, raw_moves) = input.split_at(split);
+        let stacks = get_stacks(input, raw_stacks)?;
+        let num_stacks = stacks.len();
+        let moves =
+            complete(raw_moves, |i| parse_moves(i, num_stacks)).map_err(|e| e.locate(input))?;
+        check_heights(input, raw_moves, &stacks, &moves)?;
+        Ok(Supplies { stacks, moves })
+    }
+
+    fn part_one(supplies: &Self::Parsed) -> String {
+        let mut stacks = supplies.stacks.clone();
+        for m in supplies.moves.iter() {
+            stacks = m.apply_p1(stacks);
+        }
+        top_crates(&stacks)
+    }
+
+    fn part_two(supplies: &Self::Parsed) -> String {
+        let mut stacks = supplies.stacks.clone();
+        for m in supplies.moves.iter() {
+            stacks = m.apply_p2(stacks);
+        }
+        top_crates(&stacks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day05.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part_one(&parsed), "CMZ");
+        assert_eq!(Day05::part_two(&parsed), "MCD");
+    }
+
+    #[test]
+    fn test_heights() {
+        let drawing = "[A]    \n[B] [C]\n 1   2 \n\n";
+        let parsed = Day05::parse(&format!("{}move 1 from 1 to 2\n", drawing)).unwrap();
+        assert_eq!(Day05::part_one(&parsed), "BA");
+        assert_eq!(
+            Day05::parse(&format!("{}move 3 from 1 to 2\n", drawing)).err(),
+            Some(ParseError::new(5, 6, "a quantity of at most 2"))
+        );
+        assert_eq!(
+            Day05::parse(&format!("{}move 1 from 2 to 1\n", drawing)).err(),
+            Some(ParseError::new(6, 1, "moves that leave a crate on stack 2"))
+        );
+    }
+}
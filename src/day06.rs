@@ -0,0 +1,92 @@
+use nom::bytes::complete::take;
+use nom::character::complete::anychar;
+use nom::combinator::{recognize, verify};
+use nom::multi::many_till;
+use nom::IResult;
+
+use crate::error::ParseError;
+use crate::Solution;
+
+fn all_different(input: &str) -> bool {
+    let l = input.len();
+    let input = input.as_bytes();
+    for i in 0..l {
+        for j in i + 1..l {
+            if input[i] == input[j] {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn marker_parser(marker_len: usize) -> impl Fn(&str) -> IResult<&str, &str> {
+    move |input: &str| verify(take(marker_len), all_different)(input)
+}
+
+fn length_before_marker(input: &str, marker_len: usize) -> Option<usize> {
+    let (_, prefix) = recognize(many_till(anychar, marker_parser(marker_len)))(input).ok()?;
+    Some(prefix.len())
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = String;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let datastream = input.trim_end_matches(['\r', '\n']);
+        if let Some(bad) = datastream.find(|c: char| !c.is_ascii_lowercase()) {
+            return Err(ParseError::at(
+                input,
+                &datastream[bad..],
+                "a lowercase letter",
+            ));
+        }
+        // A start-of-message marker implies a start-of-packet marker before it.
+        if length_before_marker(datastream, 14).is_none() {
+            let end = &datastream[datastream.len()..];
+            return Err(ParseError::at(input, end, "a start-of-message marker"));
+        }
+        Ok(String::from(datastream))
+    }
+
+    fn part_one(input: &Self::Parsed) -> usize {
+        length_before_marker(input, 4).unwrap()
+    }
+
+    fn part_two(input: &Self::Parsed) -> usize {
+        length_before_marker(input, 14).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day06.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part_one(&parsed), 7);
+        assert_eq!(Day06::part_two(&parsed), 19);
+    }
+
+    #[test]
+    fn test_more_examples() {
+        let examples = [
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (input, one, two) in examples {
+            let parsed = Day06::parse(input).unwrap();
+            assert_eq!(Day06::part_one(&parsed), one);
+            assert_eq!(Day06::part_two(&parsed), two);
+        }
+    }
+}
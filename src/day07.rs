@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use nom::branch::alt;
+use nom::character::complete::none_of;
+use nom::combinator::{recognize, value};
+use nom::multi::many1;
+use nom::sequence::{preceded, separated_pair};
+
+use crate::error::ParseError;
+use crate::parsers::{finish, lines, tag, unsigned, IResult};
+use crate::Solution;
+
+#[derive(Clone, Debug)]
+enum CommandLineEntry {
+    CdCommand(String),
+    CdUpCommand,
+    LsCommand,
+    Dir(String),
+    File(String, u32),
+}
+
+#[derive(Debug)]
+pub enum FsNode {
+    File(u32),
+    Directory(Option<HashMap<String, FsNode>>),
+}
+
+impl FsNode {
+    fn from_cli_entries(v: &[CommandLineEntry]) -> FsNode {
+        let (_, node) = Self::traverse_cli_entries(v, 2);
+        node
+    }
+
+    fn traverse_cli_entries(v: &[CommandLineEntry], mut index: usize) -> (usize, FsNode) {
+        let mut entries = HashMap::new();
+        while index < v.len() {
+            match &v[index] {
+                CommandLineEntry::Dir(name) => {
+                    entries.insert(name.clone(), FsNode::Directory(None));
+                    index += 1;
+                }
+                CommandLineEntry::File(name, size) => {
+                    entries.insert(name.clone(), FsNode::File(*size));
+                    index += 1;
+                }
+                _ => {
+                    break;
+                }
+            }
+        }
+        while index < v.len() {
+            if let CommandLineEntry::CdUpCommand = &v[index] {
+                return (index, FsNode::Directory(Some(entries)));
+            }
+            if let CommandLineEntry::CdCommand(name) = &v[index] {
+                let (new_index, entry) = Self::traverse_cli_entries(v, index + 2);
+                entries.insert(name.clone(), entry);
+                index = new_index;
+            }
+            index += 1;
+        }
+        (index, FsNode::Directory(Some(entries)))
+    }
+
+    fn get_directories_with_sizes(&self) -> (u32, Vec<u32>) {
+        match self {
+            FsNode::File(size) => (*size, vec![]),
+            FsNode::Directory(None) => unreachable!(),
+            FsNode::Directory(Some(m)) => {
+                let mut total_size = 0;
+                let mut result = Vec::new();
+                for (_, entry) in m.iter() {
+                    let (subtotal, mut dirs) = entry.get_directories_with_sizes();
+                    result.append(&mut dirs);
+                    if let FsNode::Directory(_) = entry {
+                        result.push(subtotal);
+                    }
+                    total_size += subtotal;
+                }
+                (total_size, result)
+            }
+        }
+    }
+}
+
+fn parse_rest_of_line(input: &str) -> IResult<'_, &str> {
+    recognize(many1(none_of("\n\r")))(input)
+}
+
+fn parse_cd_command(input: &str) -> IResult<'_, CommandLineEntry> {
+    let (rest, dir) = preceded(tag("$ cd "), parse_rest_of_line)(input)?;
+    Ok((rest, CommandLineEntry::CdCommand(String::from(dir))))
+}
+
+fn parse_ls_command(input: &str) -> IResult<'_, CommandLineEntry> {
+    value(CommandLineEntry::LsCommand, tag("$ ls"))(input)
+}
+
+fn parse_cd_up_command(input: &str) -> IResult<'_, CommandLineEntry> {
+    value(CommandLineEntry::CdUpCommand, tag("$ cd .."))(input)
+}
+
+fn parse_dir_entry(input: &str) -> IResult<'_, CommandLineEntry> {
+    let (rest, dir) = preceded(tag("dir "), parse_rest_of_line)(input)?;
+    Ok((rest, CommandLineEntry::Dir(String::from(dir))))
+}
+
+fn parse_file_entry(input: &str) -> IResult<'_, CommandLineEntry> {
+    let (rest, (size, filename)) = separated_pair(unsigned, tag(" "), parse_rest_of_line)(input)?;
+    Ok((rest, CommandLineEntry::File(String::from(filename), size)))
+}
+
+fn parse_entry(input: &str) -> IResult<'_, CommandLineEntry> {
+    alt((
+        parse_cd_up_command,
+        parse_cd_command,
+        parse_ls_command,
+        parse_dir_entry,
+        parse_file_entry,
+    ))(input)
+}
+
+fn parse_entries(input: &str) -> IResult<'_, Vec<CommandLineEntry>> {
+    lines(parse_entry)(input)
+}
+
+fn sorted_directory_sizes(fs: &FsNode) -> (u32, Vec<u32>) {
+    let (total_size, mut subdirs) = fs.get_directories_with_sizes();
+    subdirs.sort();
+    (total_size, subdirs)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = FsNode;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let entries = finish(input, parse_entries)?;
+        match entries.first() {
+            Some(CommandLineEntry::CdCommand(dir)) if dir == "/" => (),
+            _ => return Err(ParseError::new(1, 1, "\"$ cd /\"")),
+        }
+        Ok(FsNode::from_cli_entries(&entries))
+    }
+
+    fn part_one(fs: &Self::Parsed) -> u32 {
+        let (_, subdirs) = sorted_directory_sizes(fs);
+        subdirs.iter().filter(|&size| *size <= 100000).sum()
+    }
+
+    fn part_two(fs: &Self::Parsed) -> u32 {
+        let (total_size, subdirs) = sorted_directory_sizes(fs);
+        let free_space = 70000000 - total_size;
+        let space_needed = 30000000 - free_space;
+        *subdirs.iter().find(|&size| *size >= space_needed).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day07.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(Day07::part_one(&parsed), 95437);
+        assert_eq!(Day07::part_two(&parsed), 24933642);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos};
+use crate::Solution;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Tree {
+    height: i32,
+    visible: bool,
+}
+
+impl Tree {
+    fn new(height: i32) -> Self {
+        Tree {
+            height,
+            visible: false,
+        }
+    }
+
+    fn mark_visibilities<'a, T: Iterator<Item = &'a mut Tree>>(iterator: T) {
+        let mut max_height = -1;
+        for tree in iterator {
+            if tree.height > max_height {
+                tree.visible = true;
+                max_height = tree.height;
+            }
+        }
+    }
+}
+
+fn count_visibility(grid: &Grid<Tree>) -> i32 {
+    grid.iter().filter(|(_, t)| t.visible).count() as i32
+}
+
+fn mark_visibility(mut grid: Grid<Tree>) -> Grid<Tree> {
+    for y in 0..grid.height() {
+        Tree::mark_visibilities(grid.row_mut(y));
+        Tree::mark_visibilities(grid.row_mut(y).rev());
+    }
+    for x in 0..grid.width() {
+        Tree::mark_visibilities(grid.column_mut(x));
+        Tree::mark_visibilities(grid.column_mut(x).rev());
+    }
+    grid
+}
+
+fn get_viewing_distance(grid: &Grid<Tree>, origin: Pos, d: (isize, isize)) -> i32 {
+    let threshold = grid[origin].height;
+    let mut pos = origin;
+
+    let mut distance = 0;
+    while let Some(next) = grid.offset(pos, d) {
+        distance += 1;
+        if grid[next].height >= threshold {
+            break;
+        }
+        pos = next;
+    }
+    distance
+}
+
+fn get_scenic_score(grid: &Grid<Tree>, origin: Pos) -> i32 {
+    let mut score = 1;
+    for d in [(0, 1), (0, -1), (1, 0), (-1, 0)].iter() {
+        score *= get_viewing_distance(grid, origin, *d);
+    }
+    score
+}
+
+fn get_max_scenic_score(grid: &Grid<Tree>) -> i32 {
+    grid.positions()
+        .map(|pos| get_scenic_score(grid, pos))
+        .max()
+        .unwrap_or(0)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Grid<Tree>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let grid = Grid::parse(input, "a tree height", |c| {
+            c.to_digit(10).map(|h| Tree::new(h as i32))
+        })?;
+        Ok(mark_visibility(grid))
+    }
+
+    fn part_one(grid: &Self::Parsed) -> i32 {
+        count_visibility(grid)
+    }
+
+    fn part_two(grid: &Self::Parsed) -> i32 {
+        get_max_scenic_score(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day08.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part_one(&parsed), 21);
+        assert_eq!(Day08::part_two(&parsed), 8);
+    }
+}
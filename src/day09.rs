@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+use std::io;
+
+use nom::branch::alt;
+use nom::combinator::{map, value};
+use nom::sequence::separated_pair;
+
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point2};
+use crate::parsers::{finish, lines, tag, unsigned, IResult};
+use crate::visualize::{self, Canvas, Color, Sink, Visualize};
+use crate::Solution;
+
+#[derive(Debug)]
+pub struct Motion {
+    dir: Direction,
+    dist: i64,
+}
+
+impl Motion {
+    fn apply(&self, pos: Point2) -> Point2 {
+        pos + self.dir.offset() * self.dist
+    }
+}
+
+/// One step from `from` towards `to`, diagonally if they are in neither the
+/// same row nor the same column.
+fn move_towards(from: Point2, to: Point2) -> Point2 {
+    from + (to - from).signum()
+}
+
+struct State {
+    visited: HashSet<Point2>,
+    rope: Vec<Point2>,
+}
+
+impl State {
+    fn new(rope_length: usize) -> Self {
+        let mut visited = HashSet::new();
+        visited.insert(Point2::ORIGIN);
+        let rope = vec![Point2::ORIGIN; rope_length];
+        State { visited, rope }
+    }
+
+    /// Moves the head one step towards `to`, and the rest of the rope after
+    /// it.
+    fn step_towards(&mut self, to: Point2) {
+        let l = self.rope.len();
+        self.rope[0] = move_towards(self.rope[0], to);
+        for i in 1..l {
+            if self.rope[i - 1].chebyshev(self.rope[i]) > 1 {
+                self.rope[i] = move_towards(self.rope[i], self.rope[i - 1]);
+            }
+        }
+        self.visited.insert(self.rope[l - 1]);
+    }
+
+    fn apply_motion(mut self, motion: &Motion) -> Self {
+        let new_head = motion.apply(self.rope[0]);
+        while self.rope[0] != new_head {
+            self.step_towards(new_head);
+        }
+        self
+    }
+
+    fn draw(&self, min: Point2, max: Point2) -> visualize::Frame {
+        let mut canvas = Canvas::new(min, max, Color::BLACK);
+        for &p in self.visited.iter() {
+            canvas.plot(p, Color::DARK_GRAY);
+        }
+        canvas.plot(Point2::ORIGIN, Color::GREEN);
+        // Draw from the tail up, so that the head is on top.
+        for (i, &knot) in self.rope.iter().enumerate().rev() {
+            let color = if i == 0 { Color::RED } else { Color::YELLOW };
+            canvas.plot(knot, color);
+        }
+        canvas.into_frame()
+    }
+}
+
+fn parse_direction(input: &str) -> IResult<'_, Direction> {
+    alt((
+        value(Direction::North, tag("U")),
+        value(Direction::East, tag("R")),
+        value(Direction::South, tag("D")),
+        value(Direction::West, tag("L")),
+    ))(input)
+}
+
+fn parse_motion(input: &str) -> IResult<'_, Motion> {
+    let make_motion = |(dir, dist)| Motion { dir, dist };
+    let parse_raw_motion = separated_pair(parse_direction, tag(" "), unsigned);
+    map(parse_raw_motion, make_motion)(input)
+}
+
+fn parse_motions(input: &str) -> IResult<'_, Vec<Motion>> {
+    lines(parse_motion)(input)
+}
+
+fn count_tail_visits(motions: &[Motion], rope_length: usize) -> usize {
+    let mut state = State::new(rope_length);
+    for motion in motions.iter() {
+        state = state.apply_motion(motion);
+    }
+    state.visited.len()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed = Vec<Motion>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        finish(input, parse_motions)
+    }
+
+    fn part_one(motions: &Self::Parsed) -> usize {
+        count_tail_visits(motions, 2)
+    }
+
+    fn part_two(motions: &Self::Parsed) -> usize {
+        count_tail_visits(motions, 10)
+    }
+}
+
+/// Shows the ten knot rope of part two, one frame per step of the head.
+impl Visualize for Day09 {
+    fn visualize(motions: &Self::Parsed, sink: &mut dyn Sink) -> io::Result<()> {
+        // Every knot stays within the box the head has been over.
+        let heads = motions.iter().scan(Point2::ORIGIN, |head, motion| {
+            *head = motion.apply(*head);
+            Some(*head)
+        });
+        let (min, max) = visualize::bounds(heads.chain([Point2::ORIGIN])).unwrap();
+
+        let mut state = State::new(10);
+        visualize::offer(sink, || state.draw(min, max))?;
+        for motion in motions.iter() {
+            let new_head = motion.apply(state.rope[0]);
+            while state.rope[0] != new_head {
+                state.step_towards(new_head);
+                visualize::offer(sink, || state.draw(min, max))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day09.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day09::parse(EXAMPLE).unwrap();
+        assert_eq!(Day09::part_one(&parsed), 13);
+        assert_eq!(Day09::part_two(&parsed), 1);
+    }
+
+    #[test]
+    fn test_larger_example() {
+        let parsed = Day09::parse("R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n").unwrap();
+        assert_eq!(Day09::part_two(&parsed), 36);
+    }
+}
@@ -0,0 +1,144 @@
+use nom::branch::alt;
+use nom::combinator::{map, value};
+use nom::sequence::preceded;
+
+use crate::error::ParseError;
+use crate::parsers::{finish, lines, signed, tag, IResult};
+use crate::Solution;
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+fn parse_noop(input: &str) -> IResult<'_, Instruction> {
+    value(Instruction::Noop, tag("noop"))(input)
+}
+
+fn parse_addx(input: &str) -> IResult<'_, Instruction> {
+    let raw_parser = preceded(tag("addx "), signed);
+    map(raw_parser, Instruction::Addx)(input)
+}
+
+fn parse_instruction(input: &str) -> IResult<'_, Instruction> {
+    alt((parse_addx, parse_noop))(input)
+}
+
+fn parse_instructions(input: &str) -> IResult<'_, Vec<Instruction>> {
+    lines(parse_instruction)(input)
+}
+
+struct Tube {
+    instructions: Vec<Instruction>,
+    next_instruction: usize,
+    x_register: i32,
+    queue: Vec<i32>,
+}
+
+impl Tube {
+    fn new(instructions: Vec<Instruction>) -> Self {
+        Tube {
+            instructions,
+            next_instruction: 0,
+            x_register: 1,
+            queue: Vec::new(),
+        }
+    }
+}
+
+impl Iterator for Tube {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.queue.pop() {
+            Some(val) => Some(val),
+            None => {
+                if self.instructions.len() <= self.next_instruction {
+                    return None;
+                }
+                let result = match self.instructions[self.next_instruction] {
+                    Instruction::Noop => Some(self.x_register),
+                    Instruction::Addx(argument) => {
+                        self.queue.push(self.x_register);
+                        self.queue.push(self.x_register);
+                        self.x_register += argument;
+                        self.queue.pop()
+                    }
+                };
+                self.next_instruction += 1;
+                result
+            }
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Vec<Instruction>;
+    type PartOne = i32;
+    type PartTwo = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        finish(input, parse_instructions)
+    }
+
+    fn part_one(instructions: &Self::Parsed) -> i32 {
+        let tube = Tube::new(instructions.clone());
+
+        let mut interesting_states = vec![220, 180, 140, 100, 60, 20];
+        let mut total_signal_strength = 0;
+        let mut next_eval_part_one = interesting_states.pop().unwrap();
+
+        for (i, val) in tube.enumerate() {
+            let cycle = i as i32 + 1;
+            if cycle == next_eval_part_one {
+                total_signal_strength += next_eval_part_one * val;
+                if let Some(n) = interesting_states.pop() {
+                    next_eval_part_one = n;
+                }
+            }
+        }
+        total_signal_strength
+    }
+
+    fn part_two(instructions: &Self::Parsed) -> String {
+        let tube = Tube::new(instructions.clone());
+        let mut crt_display = Vec::new();
+
+        for (i, val) in tube.enumerate() {
+            let column = (i as i32) % 40;
+            let pixel = if (val - column).abs() <= 1 { '#' } else { ' ' };
+            crt_display.push(pixel);
+        }
+
+        let lines: Vec<String> = crt_display
+            .chunks(40)
+            .map(|line| line.iter().collect())
+            .collect();
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day10.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part_one(&parsed), 13140);
+        let image = [
+            "##  ##  ##  ##  ##  ##  ##  ##  ##  ##  ",
+            "###   ###   ###   ###   ###   ###   ### ",
+            "####    ####    ####    ####    ####    ",
+            "#####     #####     #####     #####     ",
+            "######      ######      ######      ####",
+            "#######       #######       #######     ",
+        ];
+        assert_eq!(Day10::part_two(&parsed), image.join("\n"));
+    }
+}
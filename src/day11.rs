@@ -0,0 +1,258 @@
+use nom::branch::alt;
+use nom::character::complete::line_ending;
+use nom::combinator::{map, value, verify};
+use nom::error::context;
+use nom::sequence::{delimited, pair, tuple};
+
+use crate::error::ParseError;
+use crate::parsers::{blocks, finish, separated, tag, unsigned, IResult};
+use crate::trace::event;
+use crate::Solution;
+
+type Item = u64;
+
+#[derive(Clone, Copy, Debug)]
+enum Operator {
+    Add,
+    Mul,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Operand {
+    Old,
+    Scalar(Item),
+}
+
+fn operand_value(operand: &Operand, old: Item) -> Item {
+    match operand {
+        Operand::Old => old,
+        Operand::Scalar(n) => *n,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Operation {
+    operator: Operator,
+    operands: (Operand, Operand),
+}
+
+impl Operation {
+    fn evaluate(&self, old: Item) -> Item {
+        let a = operand_value(&self.operands.0, old);
+        let b = operand_value(&self.operands.1, old);
+        match self.operator {
+            Operator::Add => a + b,
+            Operator::Mul => a * b,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    items: Vec<Item>,
+    op: Operation,
+    modulus: u64,
+    monkey_true: usize,
+    monkey_false: usize,
+    inspection_count: u64,
+}
+
+fn parse_add(input: &str) -> IResult<'_, Operator> {
+    value(Operator::Add, tag("+"))(input)
+}
+
+fn parse_mul(input: &str) -> IResult<'_, Operator> {
+    value(Operator::Mul, tag("*"))(input)
+}
+
+fn parse_operator(input: &str) -> IResult<'_, Operator> {
+    alt((parse_add, parse_mul))(input)
+}
+
+fn parse_operand_old(input: &str) -> IResult<'_, Operand> {
+    value(Operand::Old, tag("old"))(input)
+}
+
+fn parse_operand_scalar(input: &str) -> IResult<'_, Operand> {
+    map(unsigned, Operand::Scalar)(input)
+}
+
+fn parse_operand(input: &str) -> IResult<'_, Operand> {
+    alt((parse_operand_old, parse_operand_scalar))(input)
+}
+
+fn parse_opening_line(input: &str) -> IResult<'_, usize> {
+    delimited(tag("Monkey "), unsigned, pair(tag(":"), line_ending))(input)
+}
+
+fn parse_starting_items_line(input: &str) -> IResult<'_, Vec<Item>> {
+    let items_parser = separated(tag(", "), unsigned);
+    delimited(tag("  Starting items: "), items_parser, line_ending)(input)
+}
+
+fn parse_operation_line(input: &str) -> IResult<'_, Operation> {
+    let (rest, (_, operand1, _, operator, _, operand2, _)) = tuple((
+        tag("  Operation: new = "),
+        parse_operand,
+        tag(" "),
+        parse_operator,
+        tag(" "),
+        parse_operand,
+        line_ending,
+    ))(input)?;
+    let operation = Operation {
+        operator,
+        operands: (operand1, operand2),
+    };
+    Ok((rest, operation))
+}
+
+fn parse_modulus_line(input: &str) -> IResult<'_, Item> {
+    let divisor = context("a non-zero divisor", verify(unsigned, |&n: &Item| n > 0));
+    delimited(tag("  Test: divisible by "), divisor, line_ending)(input)
+}
+
+fn parse_monkey_true_line(input: &str) -> IResult<'_, usize> {
+    delimited(tag("    If true: throw to monkey "), unsigned, line_ending)(input)
+}
+
+fn parse_monkey_false_line(input: &str) -> IResult<'_, usize> {
+    delimited(tag("    If false: throw to monkey "), unsigned, line_ending)(input)
+}
+
+fn parse_monkey(input: &str) -> IResult<'_, Monkey> {
+    let (rest, (_, mut items, op, modulus, monkey_true, monkey_false)) = tuple((
+        parse_opening_line,
+        parse_starting_items_line,
+        parse_operation_line,
+        parse_modulus_line,
+        parse_monkey_true_line,
+        parse_monkey_false_line,
+    ))(input)?;
+    items.reserve(2000);
+    let monkey = Monkey {
+        items,
+        op,
+        modulus,
+        monkey_true,
+        monkey_false,
+        inspection_count: 0,
+    };
+    Ok((rest, monkey))
+}
+
+fn parse_monkeys(input: &str) -> IResult<'_, Vec<Monkey>> {
+    blocks(parse_monkey)(input)
+}
+
+/// Makes sure every monkey only throws to monkeys that exist, pointing at the
+/// offending "If true"/"If false" line otherwise.
+fn check_targets(input: &str, monkeys: &[Monkey]) -> Result<(), ParseError> {
+    let lines = input.lines().filter(|l| l.trim_start().starts_with("If "));
+    let targets = monkeys.iter().flat_map(|m| [m.monkey_true, m.monkey_false]);
+    for (line, target) in lines.zip(targets) {
+        if target >= monkeys.len() {
+            let number = &line[line.rfind(' ').map(|i| i + 1).unwrap_or(0)..];
+            let expected = format!("a monkey number below {}", monkeys.len());
+            return Err(ParseError::at(input, number, expected));
+        }
+    }
+    Ok(())
+}
+
+fn play_turn(
+    mut monkeys: Vec<Monkey>,
+    thrower: usize,
+    modulus: Option<Item>,
+    buffer: &mut Vec<(usize, Item)>,
+) -> Vec<Monkey> {
+    let m = &monkeys[thrower];
+    let mut count = m.inspection_count;
+    event!(Debug, "turn", monkey = thrower, items = m.items.len());
+
+    for item in m.items.iter() {
+        let item = m.op.evaluate(*item);
+        let item = if let Some(part2) = modulus {
+            item % part2
+        } else {
+            item / 3
+        };
+        let catcher = if item % m.modulus == 0 {
+            m.monkey_true
+        } else {
+            m.monkey_false
+        };
+        event!(Trace, "throw", monkey = thrower, item = item, to = catcher);
+        buffer.push((catcher, item));
+        count += 1;
+    }
+
+    for (catcher, item) in buffer.iter() {
+        monkeys[*catcher].items.push(*item);
+    }
+    buffer.clear();
+
+    monkeys[thrower].items.clear();
+    monkeys[thrower].inspection_count = count;
+    monkeys
+}
+
+fn play_round(
+    mut monkeys: Vec<Monkey>,
+    modulus: Option<Item>,
+    buffer: &mut Vec<(usize, Item)>,
+) -> Vec<Monkey> {
+    let l = monkeys.len();
+    for i in 0..l {
+        monkeys = play_turn(monkeys, i, modulus, buffer);
+    }
+    monkeys
+}
+
+fn play_rounds(mut input: Vec<Monkey>, count: u64, modulus: Option<Item>) -> u64 {
+    let mut buffer = Vec::new();
+    for _ in 0..count {
+        input = play_round(input, modulus, &mut buffer);
+    }
+    let mut counts: Vec<u64> = input.iter().map(|m| m.inspection_count).collect();
+    counts.sort();
+    counts.reverse();
+    counts[0] * counts[1]
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Vec<Monkey>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let monkeys = finish(input, parse_monkeys)?;
+        check_targets(input, &monkeys)?;
+        Ok(monkeys)
+    }
+
+    fn part_one(monkeys: &Self::Parsed) -> u64 {
+        play_rounds(monkeys.clone(), 20, None)
+    }
+
+    fn part_two(monkeys: &Self::Parsed) -> u64 {
+        let modulus = monkeys.iter().map(|m| m.modulus).product();
+        play_rounds(monkeys.clone(), 10000, Some(modulus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day11.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part_one(&parsed), 10605);
+        assert_eq!(Day11::part_two(&parsed), 2713310158);
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos as Position};
+use crate::search;
+use crate::{NoAnswer, Solution};
+
+fn height(square: char) -> u8 {
+    match square {
+        'S' => 0,
+        'E' => 25,
+        c => c as u8 - b'a',
+    }
+}
+
+pub struct HeightMap {
+    begin: Position,
+    end: Position,
+    map: Grid<u8>,
+}
+
+impl HeightMap {
+    fn height_at(&self, pos: Position) -> u8 {
+        self.map[pos]
+    }
+    /// The shortest path, backwards from `from`, to `to` or (for part two)
+    /// to any square of the lowest height. Each step may go up any amount
+    /// but down by at most one. There may be none, if the goal is walled off.
+    fn find_shortest_path(
+        &self,
+        from: Position,
+        to: Position,
+        part_two: bool,
+    ) -> Option<Vec<Position>> {
+        let neighbours = |&pos: &Position| {
+            self.map
+                .neighbours4(pos)
+                .filter(move |&n| self.height_at(n) + 1 >= self.height_at(pos))
+        };
+        let is_goal = |&pos: &Position| (part_two && self.height_at(pos) == 0) || pos == to;
+        search::bfs([from], neighbours, is_goal).path()
+    }
+
+    fn fewest_steps(&self, part_two: bool) -> Steps {
+        match self.find_shortest_path(self.end, self.begin, part_two) {
+            Some(path) => Steps::Steps(path.len() - 1),
+            None => Steps::NoRoute,
+        }
+    }
+
+    fn find(input: &str, squares: &Grid<char>, needle: char) -> Result<Position, ParseError> {
+        squares.position(|&c| c == needle).ok_or_else(|| {
+            let expected = format!("a square marked {}", needle);
+            ParseError::at(input, &input[input.len()..], expected)
+        })
+    }
+
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let squares = Grid::parse(input, "a height (a-z, S or E)", |c| {
+            matches!(c, 'a'..='z' | 'S' | 'E').then_some(c)
+        })?;
+        let begin = Self::find(input, &squares, 'S')?;
+        let end = Self::find(input, &squares, 'E')?;
+        let map = squares.map(|&c| height(c));
+        Ok(HeightMap { begin, end, map })
+    }
+}
+
+/// The answer to either part, which a map with no route to the goal lacks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Steps {
+    Steps(usize),
+    NoRoute,
+}
+
+impl fmt::Display for Steps {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Steps::Steps(steps) => write!(f, "{}", steps),
+            Steps::NoRoute => write!(f, "{}", NoAnswer),
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = HeightMap;
+    type PartOne = Steps;
+    type PartTwo = Steps;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        HeightMap::new(input)
+    }
+
+    fn part_one(map: &Self::Parsed) -> Steps {
+        map.fewest_steps(false)
+    }
+
+    fn part_two(map: &Self::Parsed) -> Steps {
+        map.fewest_steps(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day12.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part_one(&parsed), Steps::Steps(31));
+        assert_eq!(Day12::part_two(&parsed), Steps::Steps(29));
+    }
+
+    #[test]
+    fn test_no_route() {
+        // E is walled off by squares too high to climb from either side.
+        let parsed = Day12::parse("Sbz\nazE\n").unwrap();
+        assert_eq!(Day12::part_one(&parsed), Steps::NoRoute);
+        assert_eq!(Day12::part_one(&parsed).to_string(), "-");
+        assert_eq!(Day12::part_two(&parsed), Steps::NoRoute);
+    }
+}
@@ -0,0 +1,159 @@
+use std::cmp::Ordering;
+
+use nom::branch::alt;
+use nom::character::complete::line_ending;
+use nom::combinator::{map, success};
+use nom::sequence::{delimited, separated_pair};
+
+use crate::error::ParseError;
+use crate::parsers::{blocks, finish, separated, tag, unsigned, IResult};
+use crate::Solution;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Value {
+    List(Vec<Value>),
+    Integer(u32),
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self {
+            Value::List(left) => match other {
+                Value::List(right) => {
+                    if left.is_empty() || right.is_empty() {
+                        left.len().cmp(&right.len())
+                    } else {
+                        let mut index = 0;
+                        let mut result = Ordering::Equal;
+                        while let Ordering::Equal = result {
+                            result = left[index].cmp(&right[index]);
+                            index += 1;
+                            if left.len() == index || right.len() == index {
+                                if let Ordering::Equal = result {
+                                    result = left.len().cmp(&right.len());
+                                    break;
+                                }
+                            }
+                        }
+                        result
+                    }
+                }
+                Value::Integer(_) => {
+                    let right = Value::List(vec![other.clone()]);
+                    self.cmp(&right)
+                }
+            },
+            Value::Integer(left) => match other {
+                Value::List(_) => {
+                    let left = Value::List(vec![Value::Integer(*left)]);
+                    left.cmp(other)
+                }
+                Value::Integer(right) => left.cmp(right),
+            },
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn parse_integer(input: &str) -> IResult<'_, Value> {
+    map(unsigned, Value::Integer)(input)
+}
+
+fn parse_list(input: &str) -> IResult<'_, Value> {
+    let inner = alt((separated(tag(","), parse_value), success(Vec::new())));
+    let outer = delimited(tag("["), inner, tag("]"));
+    map(outer, Value::List)(input)
+}
+
+fn parse_value(input: &str) -> IResult<'_, Value> {
+    alt((parse_list, parse_integer))(input)
+}
+
+fn parse_pair(input: &str) -> IResult<'_, (Value, Value)> {
+    separated_pair(parse_list, line_ending, parse_list)(input)
+}
+
+fn parse_pairs(input: &str) -> IResult<'_, Vec<(Value, Value)>> {
+    blocks(parse_pair)(input)
+}
+
+fn pairs_to_vec(v: &[(Value, Value)]) -> Vec<Value> {
+    let v: Vec<Vec<Value>> = v.iter().map(|(v, w)| vec![v.clone(), w.clone()]).collect();
+    v.into_iter().flatten().collect()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Vec<(Value, Value)>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        finish(input, parse_pairs)
+    }
+
+    fn part_one(pairs: &Self::Parsed) -> usize {
+        let mut index_sum = 0;
+        for (i, (left, right)) in pairs.iter().enumerate() {
+            if left < right {
+                index_sum += i + 1;
+            }
+        }
+        index_sum
+    }
+
+    fn part_two(pairs: &Self::Parsed) -> usize {
+        let divider_a = Value::List(vec![Value::List(vec![Value::Integer(2)])]);
+        let divider_b = Value::List(vec![Value::List(vec![Value::Integer(6)])]);
+
+        let mut packets = pairs_to_vec(pairs);
+        packets.push(divider_a.clone());
+        packets.push(divider_b.clone());
+        packets.sort();
+
+        let a = packets.binary_search(&divider_a).unwrap() + 1;
+        let b = packets.binary_search(&divider_b).unwrap() + 1;
+        a * b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::generate::{self, Rng};
+
+    const EXAMPLE: &str = include_str!("../data/examples/day13.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part_one(&parsed), 13);
+        assert_eq!(Day13::part_two(&parsed), 140);
+    }
+
+    #[test]
+    fn test_cmp_is_total_order() {
+        for seed in 0..10 {
+            let input = generate::day13(&mut Rng::new(seed), 10);
+            let values = pairs_to_vec(&Day13::parse(&input).unwrap());
+            for a in values.iter() {
+                assert_eq!(a.cmp(a), Ordering::Equal);
+                for b in values.iter() {
+                    assert_eq!(a.cmp(b), b.cmp(a).reverse());
+                    for c in values.iter() {
+                        if a <= b && b <= c {
+                            assert!(a <= c, "{:?} <= {:?} <= {:?}", a, b, c);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
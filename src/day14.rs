@@ -0,0 +1,205 @@
+use std::io;
+
+use nom::combinator::map;
+use nom::sequence::separated_pair;
+
+use crate::error::ParseError;
+use crate::geometry::Point2;
+use crate::grid::{Grid, Pos};
+use crate::parsers::{finish, lines, separated, tag, unsigned, IResult};
+use crate::visualize::{self, Color, Sink, Visualize};
+use crate::Solution;
+
+type Polyline = Vec<Point2>;
+
+fn parse_point(input: &str) -> IResult<'_, Point2> {
+    map(separated_pair(unsigned, tag(","), unsigned), Point2::from)(input)
+}
+
+fn parse_polyline(input: &str) -> IResult<'_, Polyline> {
+    separated(tag(" -> "), parse_point)(input)
+}
+
+fn parse_polylines(input: &str) -> IResult<'_, Vec<Polyline>> {
+    lines(parse_polyline)(input)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// The part of the cave that sand can reach. Sand spreads out at most one
+/// column for every row it falls, so it never gets further from the source
+/// than the floor is deep.
+#[derive(Clone)]
+pub struct Cave {
+    tiles: Grid<Tile>,
+    source: Pos,
+    max_y: usize,
+}
+
+impl Cave {
+    fn new(polylines: &[Polyline]) -> Self {
+        let points = || polylines.iter().flatten();
+        let max_y = points().map(|p| p.y).max().unwrap_or(0);
+        let left = points()
+            .map(|p| p.x)
+            .min()
+            .unwrap_or(500)
+            .min(500 - max_y - 2);
+        let right = points()
+            .map(|p| p.x)
+            .max()
+            .unwrap_or(500)
+            .max(500 + max_y + 2);
+
+        let to_pos = |p: Point2| ((p.x - left) as usize, p.y as usize);
+        let mut tiles = Grid::new((right - left + 1) as usize, max_y as usize + 3, Tile::Air);
+        for polyline in polylines.iter() {
+            for segment in polyline.windows(2) {
+                let ((a, b), (p, q)) = (to_pos(segment[0]), to_pos(segment[1]));
+                for y in b.min(q)..=b.max(q) {
+                    for x in a.min(p)..=a.max(p) {
+                        tiles[(x, y)] = Tile::Rock;
+                    }
+                }
+            }
+        }
+        Cave {
+            tiles,
+            source: to_pos(Point2::new(500, 0)),
+            max_y: max_y as usize,
+        }
+    }
+
+    fn count_sand(&self) -> usize {
+        self.tiles.iter().filter(|(_, &t)| t == Tile::Sand).count()
+    }
+
+    fn draw(&self) -> visualize::Frame {
+        let mut frame = self.tiles.map(|tile| match tile {
+            Tile::Air => Color::BLACK,
+            Tile::Rock => Color::GRAY,
+            Tile::Sand => Color::YELLOW,
+        });
+        frame[self.source] = Color::RED;
+        frame
+    }
+}
+
+/// Drops one unit of sand from the source, returning whether it came to
+/// rest rather than falling into the abyss or finding the source blocked.
+fn drop_sand(cave: &mut Cave) -> bool {
+    if cave.tiles[cave.source] != Tile::Air {
+        return false;
+    }
+    let (mut x, mut y) = cave.source;
+    loop {
+        if y > cave.max_y {
+            return false;
+        }
+        let below = [x, x - 1, x + 1].map(|x| (x, y + 1));
+        match below.into_iter().find(|&p| cave.tiles[p] == Tile::Air) {
+            Some(next) => (x, y) = next,
+            None => break,
+        }
+    }
+    cave.tiles[(x, y)] = Tile::Sand;
+    true
+}
+
+fn simulate_sand_part_one(cave: &mut Cave) {
+    while drop_sand(cave) {}
+}
+
+/// Fills every tile sand can reach above the floor, calling `on_fill` after
+/// each one.
+fn simulate_sand_part_two(cave: &mut Cave, mut on_fill: impl FnMut(&Cave)) {
+    let mut queue = vec![cave.source];
+    while let Some((x, y)) = queue.pop() {
+        cave.tiles[(x, y)] = Tile::Sand;
+        on_fill(cave);
+        if y == cave.max_y + 1 {
+            continue;
+        }
+        for p in [x, x - 1, x + 1].map(|x| (x, y + 1)) {
+            if cave.tiles[p] == Tile::Air {
+                queue.push(p);
+            }
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = Cave;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let polylines = finish(input, parse_polylines)?;
+        Ok(Cave::new(&polylines))
+    }
+
+    fn part_one(cave: &Self::Parsed) -> usize {
+        let mut cave = cave.clone();
+        simulate_sand_part_one(&mut cave);
+        cave.count_sand()
+    }
+
+    fn part_two(cave: &Self::Parsed) -> usize {
+        let mut cave = cave.clone();
+        simulate_sand_part_two(&mut cave, |_| ());
+        cave.count_sand()
+    }
+}
+
+/// Shows part one a unit of sand at a time, then part two filling the cave
+/// in a tile at a time.
+impl Visualize for Day14 {
+    fn visualize(cave: &Self::Parsed, sink: &mut dyn Sink) -> io::Result<()> {
+        let mut first = cave.clone();
+        visualize::offer(sink, || first.draw())?;
+        while drop_sand(&mut first) {
+            visualize::offer(sink, || first.draw())?;
+        }
+
+        let mut second = cave.clone();
+        let mut result = Ok(());
+        simulate_sand_part_two(&mut second, |cave| {
+            if result.is_ok() {
+                result = visualize::offer(sink, || cave.draw());
+            }
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::generate::{self, Rng};
+
+    const EXAMPLE: &str = include_str!("../data/examples/day14.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part_one(&parsed), 24);
+        assert_eq!(Day14::part_two(&parsed), 93);
+    }
+
+    #[test]
+    fn test_random_inputs() {
+        for seed in 0..10 {
+            let input = generate::day14(&mut Rng::new(seed), 8);
+            let rocks = Day14::parse(&input).unwrap();
+            assert!(Day14::part_one(&rocks) < Day14::part_two(&rocks));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use nom::combinator::map;
+use nom::sequence::{preceded, separated_pair};
+
+use crate::error::ParseError;
+use crate::geometry::Point2;
+use crate::interval::IntervalSet;
+use crate::parsers::{finish, lines, signed, tag, IResult};
+use crate::Solution;
+
+/// The row part one counts positions on, and the largest coordinate part two
+/// searches for the distress beacon. The puzzle's example uses 10 and 20.
+const ROW: i64 = 2000000;
+const SEARCH_MAX: i64 = 4000000;
+
+/// Turns the plane 45 degrees (and scales it), so that the diamond of points
+/// within some Manhattan distance of a sensor becomes a square.
+fn rotate(p: Point2) -> Point2 {
+    Point2::new(p.x - p.y, p.x + p.y)
+}
+
+fn unrotate(p: Point2) -> Point2 {
+    Point2::new((p.x + p.y) / 2, (p.y - p.x) / 2)
+}
+
+struct Rect {
+    ge: Point2,
+    lt: Point2,
+}
+
+#[derive(Debug)]
+pub struct Pair {
+    sensor: Point2,
+    beacon: Point2,
+}
+
+impl Pair {
+    fn dist(&self) -> i64 {
+        self.sensor.manhattan(self.beacon)
+    }
+
+    fn possible_beacons_at_y(&self, y: i64) -> Range<i64> {
+        let reach = self.dist() - (self.sensor.y - y).abs();
+        self.sensor.x - reach..self.sensor.x + reach + 1
+    }
+
+    fn rotated_rect(&self) -> Rect {
+        let dist = self.dist();
+        let centre = rotate(self.sensor);
+        let ge = centre - Point2::new(dist, dist);
+        let lt = centre + Point2::new(dist + 1, dist + 1);
+        Rect { ge, lt }
+    }
+}
+
+fn rects_to_slices(rects: &[Rect]) -> (Vec<i64>, Vec<i64>) {
+    let mut horiz = HashSet::new();
+    let mut vert = HashSet::new();
+    for r in rects.iter() {
+        horiz.insert(r.ge.x);
+        horiz.insert(r.lt.x);
+        vert.insert(r.ge.y);
+        vert.insert(r.lt.y);
+    }
+    let mut horiz: Vec<i64> = horiz.into_iter().collect();
+    let mut vert: Vec<i64> = vert.into_iter().collect();
+    horiz.sort();
+    vert.sort();
+    (horiz, vert)
+}
+
+fn slice_to_units(slice: &[i64]) -> Vec<i64> {
+    let mut result = Vec::new();
+    for i in 1..slice.len() {
+        let (x1, x2) = (slice[i - 1], slice[i]);
+        if x2 - x1 == 1 {
+            result.push(x1);
+        }
+    }
+    result
+}
+
+fn slices_to_unit_squares(slices: (Vec<i64>, Vec<i64>)) -> Vec<Point2> {
+    let mut result = Vec::new();
+    for x in slice_to_units(&slices.0).iter() {
+        for y in slice_to_units(&slices.1).iter() {
+            result.push(Point2::new(*x, *y));
+        }
+    }
+    result
+}
+
+fn possible_beacons_at_y(pairs: &[Pair], y: i64) -> i64 {
+    let mut ranges: IntervalSet<i64> = pairs.iter().map(|p| p.possible_beacons_at_y(y)).collect();
+    for p in pairs.iter() {
+        if p.beacon.y == y {
+            ranges.remove_point(p.beacon.x);
+        }
+    }
+    ranges.len()
+}
+
+fn parse_point(input: &str) -> IResult<'_, Point2> {
+    let coords = separated_pair(signed, tag(", y="), signed);
+    map(preceded(tag("x="), coords), Point2::from)(input)
+}
+
+fn parse_pair(input: &str) -> IResult<'_, Pair> {
+    let inner = separated_pair(parse_point, tag(": closest beacon is at "), parse_point);
+    let mapper = |(sensor, beacon)| Pair { sensor, beacon };
+    let outer = preceded(tag("Sensor at "), inner);
+    map(outer, mapper)(input)
+}
+
+fn parse_pairs(input: &str) -> IResult<'_, Vec<Pair>> {
+    lines(parse_pair)(input)
+}
+
+fn point_not_in(p: &Point2, rects: &[Rect]) -> bool {
+    for r in rects.iter() {
+        if p.x >= r.ge.x && p.y >= r.ge.y && p.x < r.lt.x && p.y < r.lt.y {
+            return false;
+        }
+    }
+    true
+}
+
+fn distress_beacon(pairs: &[Pair], search_max: i64) -> Point2 {
+    let rects: Vec<Rect> = pairs.iter().map(|p| p.rotated_rect()).collect();
+    let squares = slices_to_unit_squares(rects_to_slices(&rects));
+    let squares: Vec<Point2> = squares
+        .into_iter()
+        .filter(|p| point_not_in(p, &rects))
+        .map(unrotate)
+        .filter(|p| (0..=search_max).contains(&p.x) && (0..=search_max).contains(&p.y))
+        .collect();
+    squares[0]
+}
+
+fn tuning_freq(p: Point2) -> i64 {
+    p.x * 4000000 + p.y
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = Vec<Pair>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        finish(input, parse_pairs)
+    }
+
+    fn part_one(pairs: &Self::Parsed) -> i64 {
+        possible_beacons_at_y(pairs, ROW)
+    }
+
+    fn part_two(pairs: &Self::Parsed) -> i64 {
+        tuning_freq(distress_beacon(pairs, SEARCH_MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day15.txt");
+
+    #[test]
+    fn test_example() {
+        let pairs = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(possible_beacons_at_y(&pairs, 10), 26);
+        assert_eq!(distress_beacon(&pairs, 20), Point2::new(14, 11));
+        assert_eq!(tuning_freq(Point2::new(14, 11)), 56000011);
+    }
+}
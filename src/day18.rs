@@ -0,0 +1,137 @@
+use std::collections::{HashMap, HashSet};
+
+use nom::sequence::tuple;
+
+use crate::error::ParseError;
+use crate::geometry::Point3;
+use crate::parsers::{finish, lines, signed, tag, IResult};
+use crate::search;
+use crate::Solution;
+
+type Voxel = Point3;
+
+fn parse_voxel(input: &str) -> IResult<'_, Voxel> {
+    let (rest, (x, _, y, _, z)) = tuple((signed, tag(","), signed, tag(","), signed))(input)?;
+    Ok((rest, Point3::new(x, y, z)))
+}
+
+fn parse_voxels(input: &str) -> IResult<'_, Vec<Voxel>> {
+    lines(parse_voxel)(input)
+}
+
+#[derive(Debug)]
+pub struct VoxelGraph {
+    vertices: HashSet<Voxel>,
+    edges: HashMap<Voxel, Vec<Voxel>>,
+    bounding_box: (Voxel, Voxel),
+}
+
+impl VoxelGraph {
+    fn get_bounding_box(vertices: &[Voxel]) -> (Voxel, Voxel) {
+        let (min, max) = vertices
+            .iter()
+            .fold((vertices[0], vertices[0]), |(min, max), &v| {
+                (min.pointwise_min(v), max.pointwise_max(v))
+            });
+        let margin = Point3::new(1, 1, 1);
+        (min - margin, max + margin)
+    }
+
+    fn from(vertices: Vec<Voxel>) -> Self {
+        let mut edges: HashMap<Voxel, Vec<Voxel>> = HashMap::new();
+
+        let bounding_box = Self::get_bounding_box(&vertices);
+
+        let vertices = HashSet::from_iter(vertices);
+        for &voxel in vertices.iter() {
+            let mut edges_current = Vec::new();
+            for neighbor in voxel.neighbours6() {
+                if vertices.contains(&neighbor) {
+                    edges_current.push(neighbor);
+                }
+            }
+            edges.insert(voxel, edges_current);
+        }
+
+        VoxelGraph {
+            vertices,
+            edges,
+            bounding_box,
+        }
+    }
+
+    fn in_bounding_box(&self, voxel: Voxel) -> bool {
+        let (min, max) = self.bounding_box;
+        voxel.pointwise_max(min) == voxel && voxel.pointwise_min(max) == voxel
+    }
+
+    /// Counts the faces the air outside the droplet touches, by flooding
+    /// the bounding box from one corner.
+    fn outer_surface_area(&self) -> usize {
+        let outside = search::reachable([self.bounding_box.0], |voxel: &Voxel| {
+            voxel
+                .neighbours6()
+                .into_iter()
+                .filter(|&n| self.in_bounding_box(n) && !self.vertices.contains(&n))
+        });
+        outside
+            .iter()
+            .flat_map(|voxel| voxel.neighbours6())
+            .filter(|n| self.vertices.contains(n))
+            .count()
+    }
+
+    fn surface_area(&self) -> usize {
+        6 * self.vertices.len() - self.count_edges()
+    }
+
+    fn count_edges(&self) -> usize {
+        self.edges.iter().map(|v| v.1.len()).sum()
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed = VoxelGraph;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(VoxelGraph::from(finish(input, parse_voxels)?))
+    }
+
+    fn part_one(graph: &Self::Parsed) -> usize {
+        graph.surface_area()
+    }
+
+    fn part_two(graph: &Self::Parsed) -> usize {
+        graph.outer_surface_area()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::generate::{self, Rng};
+
+    const EXAMPLE: &str = include_str!("../data/examples/day18.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part_one(&parsed), 64);
+        assert_eq!(Day18::part_two(&parsed), 58);
+    }
+
+    #[test]
+    fn test_random_inputs() {
+        for seed in 0..20 {
+            let input = generate::day18(&mut Rng::new(seed), 300, 10);
+            let graph = Day18::parse(&input).unwrap();
+            assert!(graph.surface_area() <= 6 * graph.vertices.len());
+            assert!(graph.surface_area() >= graph.outer_surface_area());
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::error::ParseError;
+use crate::parsers::{finish, lines, signed, IResult};
+use crate::Solution;
+
+#[derive(Copy, Clone)]
+struct CircularListItem {
+    value: i64,
+    prev: usize,
+    next: usize,
+}
+
+struct CircularList {
+    list: Vec<CircularListItem>,
+    zero_pos: usize,
+}
+
+impl CircularList {
+    fn from(v: Vec<i64>, multiplier: i64) -> Self {
+        let l = v.len();
+        let mut list = Vec::new();
+        let mut zero_pos = 0;
+        for (index, value) in v.iter().enumerate() {
+            list.push(CircularListItem {
+                value: *value * multiplier,
+                prev: (index + l - 1) % l,
+                next: (index + l + 1) % l,
+            });
+            if *value == 0 {
+                zero_pos = index;
+            }
+        }
+        CircularList { list, zero_pos }
+    }
+
+    fn yank(&mut self, index: usize) {
+        let item = self.list[index];
+        self.list[item.next].prev = item.prev;
+        self.list[item.prev].next = item.next;
+    }
+
+    fn insert_before(&mut self, index: usize, before: usize) {
+        let new_prev = self.list[before].prev;
+        let new_next = before;
+
+        self.list[before].prev = index;
+        self.list[new_prev].next = index;
+        self.list[index].next = new_next;
+        self.list[index].prev = new_prev;
+    }
+
+    fn insert_after(&mut self, index: usize, after: usize) {
+        let new_next = self.list[after].next;
+        let new_prev = after;
+
+        self.list[after].next = index;
+        self.list[new_next].prev = index;
+        self.list[index].next = new_next;
+        self.list[index].prev = new_prev;
+    }
+
+    fn mix(mut self) -> Self {
+        for index in 0..self.list.len() {
+            let value = self.list[index].value;
+            let steps = (value.unsigned_abs() as usize) % (self.list.len() - 1);
+            if value > 0 {
+                let mut after = index;
+                for _ in 0..steps {
+                    after = self.list[after].next;
+                }
+                self.yank(index);
+                self.insert_after(index, after);
+            }
+            if value < 0 {
+                let mut before = index;
+                for _ in 0..steps {
+                    before = self.list[before].prev;
+                }
+                self.yank(index);
+                self.insert_before(index, before);
+            }
+        }
+        self
+    }
+
+    fn nth_from_zero(&self, i: usize) -> i64 {
+        let mut index = self.zero_pos;
+        for _ in 0..i {
+            index = self.list[index].next;
+        }
+        self.list[index].value
+    }
+
+    fn score(&self) -> i64 {
+        let i = 1000 % self.list.len();
+        let j = 2000 % self.list.len();
+        let k = 3000 % self.list.len();
+        self.nth_from_zero(i) + self.nth_from_zero(j) + self.nth_from_zero(k)
+    }
+}
+
+fn parse_numbers(input: &str) -> IResult<'_, Vec<i64>> {
+    lines(signed)(input)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = Vec<i64>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let numbers = finish(input, parse_numbers)?;
+        let end = &input[input.len()..];
+        if numbers.len() < 2 {
+            return Err(ParseError::at(input, end, "at least two numbers"));
+        }
+        if !numbers.contains(&0) {
+            return Err(ParseError::at(input, end, "a 0 among the numbers"));
+        }
+        Ok(numbers)
+    }
+
+    fn part_one(numbers: &Self::Parsed) -> i64 {
+        let list = CircularList::from(numbers.clone(), 1);
+        list.mix().score()
+    }
+
+    fn part_two(numbers: &Self::Parsed) -> i64 {
+        let mut list = CircularList::from(numbers.clone(), 811589153);
+        for _ in 0..10 {
+            list = list.mix();
+        }
+        list.score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::generate::{self, Rng};
+
+    const EXAMPLE: &str = include_str!("../data/examples/day20.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::part_one(&parsed), 3);
+        assert_eq!(Day20::part_two(&parsed), 1623178306);
+    }
+
+    // Mixes the slow and obvious way, by moving numbers around in a `Vec`.
+    fn naive_score(numbers: &[i64], multiplier: i64, rounds: usize) -> i64 {
+        let numbers: Vec<i64> = numbers.iter().map(|n| n * multiplier).collect();
+        let mut order: Vec<usize> = (0..numbers.len()).collect();
+        let modulus = numbers.len() as i64 - 1;
+        for _ in 0..rounds {
+            for (i, n) in numbers.iter().enumerate() {
+                let from = order.iter().position(|&j| j == i).unwrap();
+                order.remove(from);
+                let to = (from as i64 + n).rem_euclid(modulus) as usize;
+                order.insert(to, i);
+            }
+        }
+        let zero = order.iter().position(|&i| numbers[i] == 0).unwrap();
+        [1000, 2000, 3000]
+            .iter()
+            .map(|k| numbers[order[(zero + k) % order.len()]])
+            .sum()
+    }
+
+    #[test]
+    fn test_random_inputs() {
+        for seed in 0..10 {
+            let input = generate::day20(&mut Rng::new(seed), 100);
+            let numbers = Day20::parse(&input).unwrap();
+            assert_eq!(Day20::part_one(&numbers), naive_score(&numbers, 1, 1));
+            assert_eq!(
+                Day20::part_two(&numbers),
+                naive_score(&numbers, 811589153, 10)
+            );
+        }
+    }
+}
@@ -0,0 +1,562 @@
+use std::fmt;
+use std::io;
+
+use nom::branch::alt;
+use nom::combinator::{map, value};
+use nom::multi::many1;
+
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point3};
+use crate::grid::{Grid, Pos};
+use crate::parsers::{complete, tag, unsigned, IResult};
+use crate::trace::event;
+use crate::visualize::{self, Color, Sink, Visualize};
+use crate::Solution;
+
+type Tiles = Grid<char>;
+
+#[derive(Clone, Copy, Debug)]
+enum TurnDir {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Instruction {
+    Forward(u64),
+    Turn(TurnDir),
+}
+
+fn facing(dir: Direction) -> u64 {
+    match dir {
+        Direction::North => 3,
+        Direction::East => 0,
+        Direction::South => 1,
+        Direction::West => 2,
+    }
+}
+
+#[derive(Debug)]
+struct Position {
+    x: usize,
+    y: usize,
+    dir: Direction,
+}
+
+/// A face of the folded cube: the top left cell of its square in the map,
+/// and the directions its outward normal and the map's right and down point
+/// in, in space.
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    origin: Pos,
+    normal: Point3,
+    right: Point3,
+    down: Point3,
+}
+
+impl Face {
+    fn axis(&self, dir: Direction) -> Point3 {
+        match dir {
+            Direction::North => -self.down,
+            Direction::East => self.right,
+            Direction::South => self.down,
+            Direction::West => -self.right,
+        }
+    }
+
+    fn direction_along(&self, axis: Point3) -> Direction {
+        Direction::ALL
+            .into_iter()
+            .find(|&dir| self.axis(dir) == axis)
+            .unwrap()
+    }
+}
+
+/// The cube the map folds into. Its layout is worked out from the map, so
+/// it works for any net and face size, not just the shape of the puzzle
+/// input.
+#[derive(Debug)]
+struct Cube {
+    size: usize,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    fn fold(grid: &Tiles) -> Option<Cube> {
+        let tiles = grid.iter().filter(|(_, &c)| c != ' ').count();
+        let size = (1..).find(|s| 6 * s * s >= tiles).unwrap();
+        if 6 * size * size != tiles {
+            return None;
+        }
+
+        let mut squares = Vec::new();
+        for y in (0..grid.height()).step_by(size) {
+            for x in (0..grid.width()).step_by(size) {
+                let square = grid.view((x, y), size, size);
+                let count = square.iter().filter(|&&c| c != ' ').count();
+                match count {
+                    0 => (),
+                    _ if count == size * size => squares.push((x, y)),
+                    _ => return None,
+                }
+            }
+        }
+
+        // Walk the net from the first face, folding each neighbour down
+        // over the shared edge: its normal becomes the way we were heading,
+        // and heading on across it points into the cube.
+        let mut faces = vec![Face {
+            origin: squares[0],
+            normal: Point3::new(0, 0, 1),
+            right: Point3::new(1, 0, 0),
+            down: Point3::new(0, 1, 0),
+        }];
+        let mut i = 0;
+        while i < faces.len() {
+            let face = faces[i];
+            let (x, y) = face.origin;
+            let neighbours = [
+                (Direction::North, y.checked_sub(size).map(|y| (x, y))),
+                (Direction::East, Some((x + size, y))),
+                (Direction::South, Some((x, y + size))),
+                (Direction::West, x.checked_sub(size).map(|x| (x, y))),
+            ];
+            for (dir, origin) in neighbours {
+                let origin = match origin {
+                    Some(origin) if squares.contains(&origin) => origin,
+                    _ => continue,
+                };
+                if faces.iter().any(|f| f.origin == origin) {
+                    continue;
+                }
+                let mut next = Face {
+                    origin,
+                    normal: face.axis(dir),
+                    ..face
+                };
+                match dir {
+                    Direction::North => next.down = face.normal,
+                    Direction::East => next.right = -face.normal,
+                    Direction::South => next.down = -face.normal,
+                    Direction::West => next.right = face.normal,
+                }
+                faces.push(next);
+            }
+            i += 1;
+        }
+
+        let mut normals: Vec<Point3> = faces.iter().map(|f| f.normal).collect();
+        normals.sort();
+        normals.dedup();
+        if squares.len() != 6 || faces.len() != 6 || normals.len() != 6 {
+            return None;
+        }
+        Some(Cube { size, faces })
+    }
+
+    fn face_at(&self, (x, y): Pos) -> &Face {
+        self.faces
+            .iter()
+            .find(|f| {
+                let (p, q) = f.origin;
+                (p..p + self.size).contains(&x) && (q..q + self.size).contains(&y)
+            })
+            .unwrap()
+    }
+
+    /// The cell one step from `pos` towards `dir`, and the direction we are
+    /// facing once there, going over an edge of the cube if need be.
+    fn step(&self, (x, y): Pos, dir: Direction) -> (Pos, Direction) {
+        let face = self.face_at((x, y));
+        let (col, row) = (x - face.origin.0, y - face.origin.1);
+        let last = self.size - 1;
+        match dir {
+            Direction::North if row > 0 => return ((x, y - 1), dir),
+            Direction::East if col < last => return ((x + 1, y), dir),
+            Direction::South if row < last => return ((x, y + 1), dir),
+            Direction::West if col > 0 => return ((x - 1, y), dir),
+            _ => (),
+        }
+
+        let next = self
+            .faces
+            .iter()
+            .find(|f| f.normal == face.axis(dir))
+            .unwrap();
+        let new_dir = next.direction_along(-face.normal);
+
+        // Both faces measure the offset along their shared edge from one of
+        // its ends; flip it if they measure from opposite ends.
+        let (along, offset) = match dir {
+            Direction::North | Direction::South => (face.right, col),
+            Direction::West | Direction::East => (face.down, row),
+        };
+        let next_along = match new_dir {
+            Direction::North | Direction::South => next.right,
+            Direction::West | Direction::East => next.down,
+        };
+        let offset = if next_along == along {
+            offset
+        } else {
+            last - offset
+        };
+
+        let (col, row) = match new_dir {
+            Direction::North => (offset, last),
+            Direction::East => (0, offset),
+            Direction::South => (offset, 0),
+            Direction::West => (last, offset),
+        };
+        let to = (next.origin.0 + col, next.origin.1 + row);
+        event!(
+            Debug,
+            "warp",
+            from = (x, y),
+            facing = dir,
+            to = to,
+            now_facing = new_dir
+        );
+        (to, new_dir)
+    }
+}
+
+impl Position {
+    fn next_cell_forward(&self, grid: &Tiles, (x, y): Pos) -> Pos {
+        let step = self.dir.offset();
+        let p = (x as i64 + step.x).rem_euclid(grid.width() as i64);
+        let q = (y as i64 + step.y).rem_euclid(grid.height() as i64);
+        (p as usize, q as usize)
+    }
+
+    fn traverse_grid(&self, grid: &Tiles, cube: Option<&Cube>) -> (Pos, Direction) {
+        if let Some(cube) = cube {
+            return cube.step((self.x, self.y), self.dir);
+        }
+        let (mut p, mut q) = self.next_cell_forward(grid, (self.x, self.y));
+        while grid[(p, q)] == ' ' {
+            (p, q) = self.next_cell_forward(grid, (p, q));
+        }
+        ((p, q), self.dir)
+    }
+
+    fn follow_instr(&mut self, grid: &Tiles, instr: Instruction, cube: Option<&Cube>) {
+        match instr {
+            Instruction::Forward(mut n) => {
+                while n > 0 {
+                    let ((p, q), dir) = self.traverse_grid(grid, cube);
+                    if grid[(p, q)] == '#' {
+                        break;
+                    }
+
+                    self.x = p;
+                    self.y = q;
+                    self.dir = dir;
+
+                    n -= 1;
+                }
+            }
+            Instruction::Turn(TurnDir::Left) => self.dir = self.dir.turn_left(),
+            Instruction::Turn(TurnDir::Right) => self.dir = self.dir.turn_right(),
+        }
+    }
+
+    fn password(&self) -> u64 {
+        let row = (self.y + 1) as u64;
+        let col = (self.x + 1) as u64;
+        let facing = facing(self.dir);
+        row * 1000 + col * 4 + facing
+    }
+}
+
+fn parse_turn_left(input: &str) -> IResult<'_, Instruction> {
+    value(Instruction::Turn(TurnDir::Left), tag("L"))(input)
+}
+
+fn parse_turn_right(input: &str) -> IResult<'_, Instruction> {
+    value(Instruction::Turn(TurnDir::Right), tag("R"))(input)
+}
+
+fn parse_forward(input: &str) -> IResult<'_, Instruction> {
+    map(unsigned, Instruction::Forward)(input)
+}
+
+fn parse_instruction(input: &str) -> IResult<'_, Instruction> {
+    alt((parse_turn_left, parse_turn_right, parse_forward))(input)
+}
+
+fn parse_instructions(input: &str) -> IResult<'_, Vec<Instruction>> {
+    many1(parse_instruction)(input)
+}
+
+fn initial_position(grid: &Tiles) -> Position {
+    let x = grid.row(0).position(|&c| c == '.').unwrap();
+    Position {
+        x,
+        y: 0,
+        dir: Direction::East,
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Tiles, Vec<Instruction>), ParseError> {
+    let mut lines = input.lines();
+    let map_end = lines
+        .by_ref()
+        .find(|l| l.is_empty())
+        .map_or(input.len(), |l| {
+            l.as_ptr() as usize - input.as_ptr() as usize
+        });
+    let grid = Grid::parse_padded(&input[..map_end], "\" \", \".\" or \"#\"", ' ', |c| {
+        matches!(c, ' ' | '.' | '#').then_some(c)
+    })?;
+    if grid.height() == 0 || !grid.row(0).any(|&c| c == '.') {
+        return Err(ParseError::new(1, 1, "an open tile on the first row"));
+    }
+
+    let end = &input[input.len()..];
+    let instr_line = lines
+        .next()
+        .ok_or_else(|| ParseError::at(input, end, "a line of instructions"))?;
+    let instructions = complete(instr_line, parse_instructions).map_err(|e| e.locate(input))?;
+    if let Some(line) = lines.find(|l| !l.is_empty()) {
+        return Err(ParseError::at(input, line, "end of input"));
+    }
+    Ok((grid, instructions))
+}
+
+fn final_password(grid: &Tiles, instructions: &[Instruction], cube: Option<&Cube>) -> u64 {
+    let mut position = initial_position(grid);
+    for instr in instructions.iter() {
+        position.follow_instr(grid, *instr, cube);
+    }
+    position.password()
+}
+
+pub struct Board {
+    grid: Tiles,
+    /// The cube the map folds into, if it does: only part two needs one.
+    cube: Option<Cube>,
+    instructions: Vec<Instruction>,
+}
+
+/// The answer to part two, which only a map that folds into a cube has.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CubePassword {
+    Password(u64),
+    NotACube,
+}
+
+impl fmt::Display for CubePassword {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CubePassword::Password(password) => write!(f, "{}", password),
+            CubePassword::NotACube => write!(f, "none, the map does not fold into a cube"),
+        }
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed = Board;
+    type PartOne = u64;
+    type PartTwo = CubePassword;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let (grid, instructions) = parse_input(input)?;
+        let cube = Cube::fold(&grid);
+        Ok(Board {
+            grid,
+            cube,
+            instructions,
+        })
+    }
+
+    fn part_one(board: &Self::Parsed) -> u64 {
+        final_password(&board.grid, &board.instructions, None)
+    }
+
+    fn part_two(board: &Self::Parsed) -> CubePassword {
+        match board.cube {
+            Some(ref cube) => {
+                let password = final_password(&board.grid, &board.instructions, Some(cube));
+                CubePassword::Password(password)
+            }
+            None => CubePassword::NotACube,
+        }
+    }
+}
+
+/// Shows the walk around the cube of part two, one frame per instruction,
+/// leaving a trail behind.
+impl Visualize for Day22 {
+    fn visualize(board: &Self::Parsed, sink: &mut dyn Sink) -> io::Result<()> {
+        let cube = board.cube.as_ref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the map does not fold into a cube",
+            )
+        })?;
+        let (grid, cube) = (&board.grid, Some(cube));
+        let mut trail = grid.map(|&c| match c {
+            '.' => Color::DARK_GRAY,
+            '#' => Color::WHITE,
+            _ => Color::BLACK,
+        });
+        let draw = |trail: &visualize::Frame, position: &Position| {
+            let mut frame = trail.clone();
+            frame[(position.x, position.y)] = Color::RED;
+            frame
+        };
+
+        let mut position = initial_position(grid);
+        visualize::offer(sink, || draw(&trail, &position))?;
+        for &instr in board.instructions.iter() {
+            match instr {
+                // Go one step at a time, to leave a trail over every cell.
+                Instruction::Forward(n) => {
+                    for _ in 0..n {
+                        trail[(position.x, position.y)] = Color::BLUE;
+                        position.follow_instr(grid, Instruction::Forward(1), cube);
+                    }
+                }
+                Instruction::Turn(_) => position.follow_instr(grid, instr, cube),
+            }
+            visualize::offer(sink, || draw(&trail, &position))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day22.txt");
+
+    // The net of the puzzle inputs, with 50 wide faces:
+    //
+    //  AB
+    //  C
+    // DE
+    // F
+    fn input_cube() -> Cube {
+        let faces = [(50, 0), (100, 0), (50, 50), (0, 100), (50, 100), (0, 150)];
+        let grid = Grid::from_fn(150, 200, |(x, y)| {
+            let on_face = |&(p, q): &Pos| (p..p + 50).contains(&x) && (q..q + 50).contains(&y);
+            if faces.iter().any(on_face) {
+                '.'
+            } else {
+                ' '
+            }
+        });
+        Cube::fold(&grid).unwrap()
+    }
+
+    #[test]
+    fn test_example() {
+        let board = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(board.cube.as_ref().unwrap().size, 4);
+        assert_eq!(Day22::part_one(&board), 6032);
+        assert_eq!(Day22::part_two(&board), CubePassword::Password(5031));
+    }
+
+    #[test]
+    fn test_not_a_cube() {
+        // Part one only walks the map, so it needs no cube.
+        let board = Day22::parse("...#\n#...\n\n1R1\n").unwrap();
+        assert!(board.cube.is_none());
+        assert_eq!(Day22::part_one(&board), 1000 * 2 + 4 * 2 + 1);
+        assert_eq!(Day22::part_two(&board), CubePassword::NotACube);
+    }
+
+    #[test]
+    fn test_a_d() {
+        let cube = input_cube();
+        assert_eq!(
+            cube.step((50, 10), Direction::West),
+            ((0, 139), Direction::East)
+        );
+        assert_eq!(
+            cube.step((0, 139), Direction::West),
+            ((50, 10), Direction::East)
+        );
+    }
+
+    #[test]
+    fn test_a_f() {
+        let cube = input_cube();
+        assert_eq!(
+            cube.step((0, 160), Direction::West),
+            ((60, 0), Direction::South)
+        );
+        assert_eq!(
+            cube.step((60, 0), Direction::North),
+            ((0, 160), Direction::East)
+        );
+    }
+
+    #[test]
+    fn test_b_c() {
+        let cube = input_cube();
+        assert_eq!(
+            cube.step((110, 49), Direction::South),
+            ((99, 60), Direction::West)
+        );
+        assert_eq!(
+            cube.step((99, 60), Direction::East),
+            ((110, 49), Direction::North)
+        );
+    }
+
+    #[test]
+    fn test_b_e() {
+        let cube = input_cube();
+        assert_eq!(
+            cube.step((149, 10), Direction::East),
+            ((99, 139), Direction::West)
+        );
+        assert_eq!(
+            cube.step((99, 110), Direction::East),
+            ((149, 39), Direction::West)
+        );
+    }
+
+    #[test]
+    fn test_b_f() {
+        let cube = input_cube();
+        assert_eq!(
+            cube.step((110, 0), Direction::North),
+            ((10, 199), Direction::North)
+        );
+        assert_eq!(
+            cube.step((10, 199), Direction::South),
+            ((110, 0), Direction::South)
+        );
+    }
+
+    #[test]
+    fn test_c_d() {
+        let cube = input_cube();
+        assert_eq!(
+            cube.step((50, 60), Direction::West),
+            ((10, 100), Direction::South)
+        );
+        assert_eq!(
+            cube.step((10, 100), Direction::North),
+            ((50, 60), Direction::East)
+        );
+    }
+
+    #[test]
+    fn test_e_f() {
+        let cube = input_cube();
+        assert_eq!(
+            cube.step((60, 149), Direction::South),
+            ((49, 160), Direction::West)
+        );
+        assert_eq!(
+            cube.step((49, 160), Direction::East),
+            ((60, 149), Direction::North)
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::io;
+
+use crate::error::ParseError;
+use crate::geometry::{Direction, Point2};
+use crate::grid::{self, Grid};
+use crate::trace::event;
+use crate::visualize::{self, Canvas, Color, Sink, Visualize};
+use crate::Solution;
+
+pub type Pos = Point2;
+
+/// Which cells hold an elf, over the elves' bounding box and a border of one
+/// cell around it, so that every elf's neighbours are on the grid.
+struct Occupancy {
+    origin: Pos,
+    cells: Grid<bool>,
+}
+
+impl Occupancy {
+    fn new(elves: &[Pos]) -> Self {
+        let (min, max) = bounding_box(elves);
+        let width = (max.x - min.x + 3) as usize;
+        let height = (max.y - min.y + 3) as usize;
+        let mut occupancy = Occupancy {
+            origin: min - Point2::new(1, 1),
+            cells: Grid::new(width, height, false),
+        };
+        for &elf in elves {
+            let cell = occupancy.cell(elf);
+            occupancy.cells[cell] = true;
+        }
+        occupancy
+    }
+
+    fn cell(&self, pos: Pos) -> grid::Pos {
+        let Point2 { x, y } = pos - self.origin;
+        (x as usize, y as usize)
+    }
+
+    fn contains(&self, pos: Pos) -> bool {
+        self.cells[self.cell(pos)]
+    }
+}
+
+fn vicinity_contains_elf(elf: Pos, occupancy: &Occupancy) -> bool {
+    let cell = occupancy.cell(elf);
+    occupancy
+        .cells
+        .neighbours8(cell)
+        .any(|p| occupancy.cells[p])
+}
+
+fn check_for_proposal(elf: Pos, occupancy: &Occupancy, dir: Direction) -> Option<Pos> {
+    let ahead = elf + dir.offset();
+    let side = [
+        ahead + dir.turn_left().offset(),
+        ahead,
+        ahead + dir.turn_right().offset(),
+    ];
+    (!side.iter().any(|&p| occupancy.contains(p))).then_some(ahead)
+}
+
+fn round(mut elves: Vec<Pos>, round_number: usize) -> (u32, Vec<Pos>) {
+    let occupancy = Occupancy::new(&elves);
+    let mut dirs: Vec<Direction> = vec![
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ];
+    dirs = dirs[round_number % 4..(round_number % 4) + 4].to_vec();
+
+    // Every proposal is next to an elf, so the occupancy grid covers them all.
+    let mut proposed = occupancy.cells.map(|_| 0u8);
+    let proposals: Vec<Option<Pos>> = elves
+        .iter()
+        .map(|&elf| {
+            if !vicinity_contains_elf(elf, &occupancy) {
+                return None;
+            }
+            let p = dirs
+                .iter()
+                .find_map(|&dir| check_for_proposal(elf, &occupancy, dir))?;
+            proposed[occupancy.cell(p)] += 1;
+            Some(p)
+        })
+        .collect();
+
+    let mut elves_moved = 0;
+    for (elf, proposal) in elves.iter_mut().zip(proposals) {
+        if let Some(p) = proposal.filter(|&p| proposed[occupancy.cell(p)] == 1) {
+            elves_moved += 1;
+            *elf = p;
+        }
+    }
+    event!(
+        Debug,
+        "round",
+        round = round_number + 1,
+        moved = elves_moved
+    );
+    (elves_moved, elves)
+}
+
+fn bounding_box(elves: &[Pos]) -> (Pos, Pos) {
+    elves.iter().fold((elves[0], elves[0]), |(min, max), &elf| {
+        (min.pointwise_min(elf), max.pointwise_max(elf))
+    })
+}
+
+fn empty_within_bb(elves: &[Pos]) -> usize {
+    let (min, max) = bounding_box(elves);
+    let w = (max.x - min.x + 1) as usize;
+    let h = (max.y - min.y + 1) as usize;
+    w * h - elves.len()
+}
+
+fn draw(elves: &[Pos], (min, max): (Pos, Pos)) -> visualize::Frame {
+    let mut canvas = Canvas::new(min, max, Color::BLACK);
+    for &elf in elves.iter() {
+        canvas.plot(elf, Color::GREEN);
+    }
+    canvas.into_frame()
+}
+
+fn parse_elves(input: &str) -> Result<Vec<Pos>, ParseError> {
+    let grid = Grid::parse(input, "\"#\" or \".\"", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let elves: Vec<Pos> = grid
+        .iter()
+        .filter(|(_, &elf)| elf)
+        .map(|((x, y), _)| Point2::new(x as i64, y as i64))
+        .collect();
+    if elves.is_empty() {
+        return Err(ParseError::at(
+            input,
+            &input[input.len()..],
+            "at least one elf",
+        ));
+    }
+    Ok(elves)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed = Vec<Pos>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_elves(input)
+    }
+
+    fn part_one(elves: &Self::Parsed) -> usize {
+        let mut elves = elves.clone();
+        for i in 0..10 {
+            (_, elves) = round(elves, i);
+        }
+        empty_within_bb(&elves)
+    }
+
+    fn part_two(elves: &Self::Parsed) -> usize {
+        let mut elves = elves.clone();
+        let mut i = 0;
+        let mut num_moved;
+        loop {
+            (num_moved, elves) = round(elves, i);
+            if num_moved == 0 {
+                return i + 1;
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Shows the elves spreading out, one frame per round until none of them
+/// moves.
+impl Visualize for Day23 {
+    fn visualize(elves: &Self::Parsed, sink: &mut dyn Sink) -> io::Result<()> {
+        // Play it through once to find how far the elves get, so that every
+        // frame can be the same size.
+        let mut bounds = bounding_box(elves);
+        let mut current = elves.clone();
+        for i in 0.. {
+            let moved;
+            (moved, current) = round(current, i);
+            let (min, max) = bounding_box(&current);
+            bounds = (bounds.0.pointwise_min(min), bounds.1.pointwise_max(max));
+            if moved == 0 {
+                break;
+            }
+        }
+
+        let mut current = elves.clone();
+        visualize::offer(sink, || draw(&current, bounds))?;
+        for i in 0.. {
+            let moved;
+            (moved, current) = round(current, i);
+            visualize::offer(sink, || draw(&current, bounds))?;
+            if moved == 0 {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/day23.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::part_one(&parsed), 110);
+        assert_eq!(Day23::part_two(&parsed), 20);
+    }
+}
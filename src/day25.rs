@@ -0,0 +1,103 @@
+use crate::error::ParseError;
+use crate::{NoAnswer, Solution};
+
+fn to_snafu(mut n: i64) -> Vec<i64> {
+    let mut result = Vec::new();
+    if n == 0 {
+        return vec![0];
+    }
+    while n > 0 {
+        let digit = (n + 2) % 5 - 2;
+        result.push(digit);
+        n -= digit;
+        n /= 5;
+    }
+    result
+}
+
+fn snafu_digit(c: char) -> Option<i64> {
+    match c {
+        '=' => Some(-2),
+        '-' => Some(-1),
+        '0' => Some(0),
+        '1' => Some(1),
+        '2' => Some(2),
+        _ => None,
+    }
+}
+
+fn from_snafu(snafu: &str) -> Option<i64> {
+    let mut result = 0;
+    for c in snafu.chars() {
+        result *= 5;
+        result += snafu_digit(c)?;
+    }
+    Some(result)
+}
+
+fn digits_to_string(digits: &[i64]) -> String {
+    let mut result = String::new();
+    for digit in digits.iter().rev() {
+        match *digit {
+            -2 => result.push('='),
+            -1 => result.push('-'),
+            _ => result.push_str(&digit.to_string()),
+        }
+    }
+    result
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Parsed = i64;
+    type PartOne = String;
+    type PartTwo = NoAnswer;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut sum = 0;
+        for line in input.lines() {
+            sum += from_snafu(line).ok_or_else(|| {
+                let bad = line.find(|c| snafu_digit(c).is_none()).unwrap_or(0);
+                ParseError::at(input, &line[bad..], "a SNAFU digit (=, -, 0, 1 or 2)")
+            })?;
+        }
+        Ok(sum)
+    }
+
+    fn part_one(sum: &Self::Parsed) -> String {
+        digits_to_string(&to_snafu(*sum))
+    }
+
+    fn part_two(_: &Self::Parsed) -> NoAnswer {
+        NoAnswer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::generate::{self, Rng};
+
+    const EXAMPLE: &str = include_str!("../data/examples/day25.txt");
+
+    #[test]
+    fn test_example() {
+        let parsed = Day25::parse(EXAMPLE).unwrap();
+        assert_eq!(Day25::part_one(&parsed), "2=-1=0");
+    }
+
+    #[test]
+    fn test_snafu_round_trip() {
+        let mut rng = Rng::new(25);
+        for _ in 0..1000 {
+            let n = rng.range(0, 1 << 50);
+            assert_eq!(from_snafu(&digits_to_string(&to_snafu(n))), Some(n));
+        }
+        for snafu in generate::day25(&mut rng, 1000, 20).lines() {
+            let n = from_snafu(snafu).unwrap();
+            assert_eq!(digits_to_string(&to_snafu(n)), snafu);
+        }
+    }
+}
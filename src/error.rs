@@ -0,0 +1,102 @@
+use std::error;
+use std::fmt;
+use std::io;
+
+/// Where an input first stopped making sense, and what should have been there.
+///
+/// Lines and columns are both counted from 1, columns in characters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Builds an error pointing at the start of `rest`, which must be a
+    /// subslice of `input`.
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = (rest.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+        let consumed = &input[..offset.min(input.len())];
+        let line = consumed.matches('\n').count() + 1;
+        let line_start = consumed.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = consumed[line_start..].chars().count() + 1;
+        ParseError::new(line, column, expected)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Everything that can go wrong when running a day: reading the input, or
+/// making sense of it.
+pub enum Error {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+// `main` prints the `Debug` representation of the error it returns, so keep
+// that readable rather than deriving it.
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let input = "abc\ndéf\nghi";
+        let rest = &input[input.find('f').unwrap()..];
+        assert_eq!(ParseError::at(input, rest, "x"), ParseError::new(2, 3, "x"));
+        assert_eq!(
+            ParseError::at(input, input, "x"),
+            ParseError::new(1, 1, "x")
+        );
+        let end = &input[input.len()..];
+        assert_eq!(ParseError::at(input, end, "x"), ParseError::new(3, 4, "x"));
+    }
+}
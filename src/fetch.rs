@@ -0,0 +1,207 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+
+/// The environment variables `aoc fetch` reads its settings from.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (aoc fetch)"
+);
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// The server answered, but not with the input.
+    Http { status: u16, message: String },
+    /// The server could not be reached, or the connection broke.
+    Transport(String),
+    /// The input could not be written to the cache.
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::Http { status, message } if message.is_empty() => {
+                write!(f, "HTTP {}", status)
+            }
+            FetchError::Http { status, message } => write!(f, "HTTP {}: {}", status, message),
+            FetchError::Transport(e) => write!(f, "{}", e),
+            FetchError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for FetchError {
+    fn from(e: io::Error) -> Self {
+        FetchError::Io(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Downloads puzzle inputs from `<base URL>/day/<day>/input`, logged in
+/// with a session cookie.
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Fetcher {
+            base_url: String::from(base_url.trim_end_matches('/')),
+            session: String::from(session.trim()),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Makes sure `path` holds the input for `day`, downloading it only if
+    /// the file is missing or empty.
+    pub fn fetch(&self, day: u8, path: &Path) -> Result<Fetched, FetchError> {
+        if fs::metadata(path).is_ok_and(|m| m.len() > 0) {
+            return Ok(Fetched::Cached);
+        }
+
+        let url = format!("{}/day/{}/input", self.base_url, day);
+        let input = match self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+        {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                // Advent of Code explains errors in a line of plain text;
+                // anything else is some proxy's error page, and not worth
+                // showing.
+                let message = match body.lines().next().map(str::trim) {
+                    Some(line) if !line.starts_with('<') => String::from(line),
+                    _ => String::new(),
+                };
+                return Err(FetchError::Http { status, message });
+            }
+            Err(ureq::Error::Transport(e)) => return Err(FetchError::Transport(e.to_string())),
+        };
+
+        // Write to a temporary file first, so that an interrupted download
+        // never leaves a partial input behind to be mistaken for a cached one.
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("part");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, path)?;
+        Ok(Fetched::Downloaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves one canned response per connection, in order, and hands back
+    /// the request heads it received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/2022", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                while reader.read_line(&mut head).unwrap() > 2 {}
+                requests.push(head);
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    /// A fresh directory for one test, which it removes when done. Tests
+    /// run in parallel, so they do not share one.
+    fn temp_dir(test: &str) -> PathBuf {
+        let name = format!("aoc-fetch-{}-{}", std::process::id(), test);
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let (base_url, server) = serve(vec![(200, "1\n2\n")]);
+        let dir = temp_dir("cache");
+        let path = dir.join("day01.txt");
+        let fetcher = Fetcher::new(&base_url, "abc\n");
+
+        assert_eq!(fetcher.fetch(1, &path).unwrap(), Fetched::Downloaded);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+        assert_eq!(fetcher.fetch(1, &path).unwrap(), Fetched::Cached);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc\r\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_http_error() {
+        let message = "Please don't repeatedly request this endpoint before it unlocks!";
+        let (base_url, server) = serve(vec![(404, message)]);
+        let dir = temp_dir("http");
+        let path = dir.join("day25.txt");
+        let fetcher = Fetcher::new(&base_url, "abc");
+
+        match fetcher.fetch(25, &path) {
+            Err(FetchError::Http { status, message: m }) => {
+                assert_eq!(status, 404);
+                assert_eq!(m, message);
+            }
+            result => panic!("expected an HTTP error, got {:?}", result),
+        }
+        assert!(!path.exists());
+        server.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_transport_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let fetcher = Fetcher::new(&base_url, "abc");
+        let dir = temp_dir("transport");
+        let result = fetcher.fetch(2, &dir.join("day02.txt"));
+        assert!(matches!(result, Err(FetchError::Transport(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+/// A seeded SplitMix64 generator, for random but reproducible puzzle inputs
+/// to test the days with beyond our own.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..=high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        let span = (high - low) as u64 + 1;
+        low + (self.next_u64() % span) as i64
+    }
+
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as i64 - 1) as usize]
+    }
+}
+
+/// Pairs of section ranges such as `2-4,6-8`.
+pub fn day04(rng: &mut Rng, pairs: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..pairs {
+        let mut range = || {
+            let start = rng.range(1, 99);
+            (start, rng.range(start, 99))
+        };
+        let ((a, b), (c, d)) = (range(), range());
+        input.push_str(&format!("{}-{},{}-{}\n", a, b, c, d));
+    }
+    input
+}
+
+fn packet(rng: &mut Rng, depth: u32) -> String {
+    let len = rng.range(0, 4);
+    let elements: Vec<String> = (0..len)
+        .map(|_| {
+            if depth < 4 && rng.chance(30) {
+                packet(rng, depth + 1)
+            } else {
+                rng.range(0, 10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", elements.join(","))
+}
+
+/// Pairs of nested list packets, separated by blank lines.
+pub fn day13(rng: &mut Rng, pairs: usize) -> String {
+    let pairs: Vec<String> = (0..pairs)
+        .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+        .collect();
+    pairs.join("\n")
+}
+
+/// Rock paths of horizontal and vertical segments below the sand source at
+/// `500,0`.
+pub fn day14(rng: &mut Rng, paths: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..paths {
+        let (mut x, mut y) = (rng.range(480, 520), rng.range(2, 30));
+        let mut points = vec![format!("{},{}", x, y)];
+        for i in 0..rng.range(1, 4) {
+            let step = rng.range(1, 6) * *rng.choose(&[-1, 1]);
+            if i % 2 == 0 {
+                x = (x + step).clamp(470, 530);
+            } else {
+                y = (y + step).clamp(2, 30);
+            }
+            points.push(format!("{},{}", x, y));
+        }
+        input.push_str(&points.join(" -> "));
+        input.push('\n');
+    }
+    input
+}
+
+/// Distinct unit cubes in a `size` wide region, as `x,y,z` lines.
+pub fn day18(rng: &mut Rng, cubes: usize, size: i64) -> String {
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+    while seen.len() < cubes.min((size * size * size) as usize) {
+        let cube = (
+            rng.range(0, size - 1),
+            rng.range(0, size - 1),
+            rng.range(0, size - 1),
+        );
+        if seen.insert(cube) {
+            input.push_str(&format!("{},{},{}\n", cube.0, cube.1, cube.2));
+        }
+    }
+    input
+}
+
+/// A list of numbers to mix, with exactly one 0 somewhere in it.
+pub fn day20(rng: &mut Rng, count: usize) -> String {
+    let count = count.max(2);
+    let zero = rng.range(0, count as i64 - 1) as usize;
+    let mut input = String::new();
+    for i in 0..count {
+        let n = if i == zero {
+            0
+        } else {
+            rng.range(1, 10000) * *rng.choose(&[-1, 1])
+        };
+        input.push_str(&format!("{}\n", n));
+    }
+    input
+}
+
+/// Positive SNAFU numbers of up to `digits` digits, without leading zeros.
+pub fn day25(rng: &mut Rng, count: usize, digits: i64) -> String {
+    let mut input = String::new();
+    for _ in 0..count {
+        input.push(*rng.choose(&['1', '2']));
+        for _ in 1..rng.range(1, digits) {
+            input.push(*rng.choose(&['=', '-', '0', '1', '2']));
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        assert_eq!(day13(&mut a, 5), day13(&mut b, 5));
+        assert_ne!(day13(&mut Rng::new(7), 5), day13(&mut Rng::new(8), 5));
+
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3, 3)));
+        }
+    }
+}
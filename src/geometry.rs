@@ -0,0 +1,314 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or offset on the plane. `y` grows downwards, as it does in the
+/// puzzles' maps, so north is `(0, -1)`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A point or offset in space.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn chebyshev(self, other: Point2) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Each coordinate reduced to -1, 0 or 1: a single step, orthogonal or
+    /// diagonal, in this offset's general direction.
+    pub fn signum(self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The smaller of each pair of coordinates.
+    pub fn pointwise_min(self, other: Point2) -> Self {
+        Point2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// The larger of each pair of coordinates.
+    pub fn pointwise_max(self, other: Point2) -> Self {
+        Point2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// A quarter turn about the origin, anticlockwise as seen on the map.
+    pub fn rotate_left(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// A quarter turn about the origin, clockwise as seen on the map.
+    pub fn rotate_right(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    pub fn neighbours4(self) -> [Point2; 4] {
+        Direction::ALL.map(|d| self + d.offset())
+    }
+
+    pub fn neighbours8(self) -> [Point2; 8] {
+        let (x, y) = (self.x, self.y);
+        [
+            Point2::new(x - 1, y - 1),
+            Point2::new(x, y - 1),
+            Point2::new(x + 1, y - 1),
+            Point2::new(x - 1, y),
+            Point2::new(x + 1, y),
+            Point2::new(x - 1, y + 1),
+            Point2::new(x, y + 1),
+            Point2::new(x + 1, y + 1),
+        ]
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(self, other: Point3) -> i64 {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    pub fn signum(self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    pub fn pointwise_min(self, other: Point3) -> Self {
+        Point3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    pub fn pointwise_max(self, other: Point3) -> Self {
+        Point3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    pub fn cross(self, other: Point3) -> Self {
+        Point3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// A quarter turn of this point about `axis`, which must be one of the
+    /// six unit offsets, anticlockwise when looking back along it.
+    pub fn rotate_about(self, axis: Point3) -> Self {
+        axis.cross(self) + axis * self.dot(axis)
+    }
+
+    pub fn dot(self, other: Point3) -> i64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// The six points that share a face with this one.
+    pub fn neighbours6(self) -> [Point3; 6] {
+        [
+            Point3::new(1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, 0, 1),
+            Point3::new(-1, 0, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 0, -1),
+        ]
+        .map(|d| self + d)
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),* }) => {
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($field: self.$field + other.$field),* }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($field: self.$field - other.$field),* }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($field: -self.$field),* }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = $point;
+
+            fn mul(self, k: i64) -> $point {
+                $point { $($field: self.$field * k),* }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl fmt::Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// A compass direction on the map, with north up.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All four directions, clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn turn_around(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    /// The unit step in this direction.
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction::North => Point2::new(0, -1),
+            Direction::East => Point2::new(1, 0),
+            Direction::South => Point2::new(0, 1),
+            Direction::West => Point2::new(-1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point2::new(1, -2), Point2::new(-3, 1));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a - b + b, a);
+        assert_eq!((b - a).signum(), Point2::new(-1, 1));
+
+        let (p, q) = (Point3::new(1, 2, 3), Point3::new(2, 0, 7));
+        assert_eq!(p.manhattan(q), 7);
+        assert_eq!(p.chebyshev(q), 4);
+        assert_eq!(-p * 2, Point3::new(-2, -4, -6));
+    }
+
+    #[test]
+    fn test_rotation() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.offset().rotate_right(), dir.turn_right().offset());
+            assert_eq!(dir.offset().rotate_left(), dir.turn_left().offset());
+            assert_eq!(dir.turn_around().offset(), -dir.offset());
+            assert_eq!(dir.turn_left().turn_right(), dir);
+        }
+
+        let (x, y, z) = (
+            Point3::new(1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, 0, 1),
+        );
+        assert_eq!(x.cross(y), z);
+        assert_eq!(x.rotate_about(z), y);
+        assert_eq!(z.rotate_about(z), z);
+        let p = Point3::new(1, 2, 3);
+        assert_eq!((0..4).fold(p, |p, _| p.rotate_about(y)), p);
+    }
+}
@@ -0,0 +1,444 @@
+use std::fmt;
+use std::iter::StepBy;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use crate::error::ParseError;
+use crate::parsers;
+
+/// A cell's column and row, counted from the top left.
+pub type Pos = (usize, usize);
+
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != width) {
+            return None;
+        }
+        let cells = rows.into_iter().flatten().collect();
+        Some(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a character map, one row per line, converting each character
+    /// with `cell`. Every line must be as wide as the first.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        parsers::finish(input, parsers::grid(expected, cell))
+    }
+
+    /// Like `parse`, but allows rows of any width, padding short ones out
+    /// with `fill`.
+    pub fn parse_padded(
+        input: &str,
+        expected: &str,
+        fill: T,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError>
+    where
+        T: Clone,
+    {
+        let mut rows = Vec::new();
+        for line in input.lines() {
+            rows.push(parse_row(input, line, expected, &mut cell)?);
+        }
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in rows.iter_mut() {
+            row.resize(width, fill.clone());
+        }
+        Ok(Self::from_rows(rows).unwrap())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        let width = self.width;
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.1 * width + pos.0])
+    }
+
+    /// The position `(dx, dy)` away from `pos`, if it is on the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The orthogonal neighbours of `pos` that are on the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .filter_map(move |&d| self.offset(pos, d))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that are on the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_8
+            .iter()
+            .filter_map(move |&d| self.offset(pos, d))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, row by row, matching `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        let i = self.cells.iter().position(&mut predicate)?;
+        Some((i % self.width, i / self.width))
+    }
+
+    pub fn row(&self, y: usize) -> slice::Iter<'_, T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> slice::IterMut<'_, T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter_mut()
+    }
+
+    pub fn column(&self, x: usize) -> StepBy<slice::Iter<'_, T>> {
+        // Past the last column, the slice would start on a later row.
+        assert!(x < self.width, "column {} of a grid {} wide", x, self.width);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn column_mut(&mut self, x: usize) -> StepBy<slice::IterMut<'_, T>> {
+        assert!(x < self.width, "column {} of a grid {} wide", x, self.width);
+        let width = self.width;
+        self.cells[x..].iter_mut().step_by(width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = slice::Iter<'_, T>> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = StepBy<slice::Iter<'_, T>>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    /// The grid mirrored along its main diagonal, so that rows become
+    /// columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        if self.width == 0 || self.height == 0 {
+            return Grid {
+                width: self.height,
+                height: self.width,
+                cells: Vec::new(),
+            };
+        }
+        let last = self.height - 1;
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, last - x)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn anticlockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        if self.width == 0 || self.height == 0 {
+            return Grid {
+                width: self.height,
+                height: self.width,
+                cells: Vec::new(),
+            };
+        }
+        let last = self.width - 1;
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(last - y, x)].clone()
+        })
+    }
+
+    /// A window onto the `width` by `height` cells starting at `origin`,
+    /// clipped to the grid.
+    pub fn view(&self, origin: Pos, width: usize, height: usize) -> View<'_, T> {
+        let (x, y) = (origin.0.min(self.width), origin.1.min(self.height));
+        View {
+            grid: self,
+            origin: (x, y),
+            width: width.min(self.width - x),
+            height: height.min(self.height - y),
+        }
+    }
+}
+
+fn parse_row<T>(
+    input: &str,
+    line: &str,
+    expected: &str,
+    cell: &mut impl FnMut(char) -> Option<T>,
+) -> Result<Vec<T>, ParseError> {
+    line.char_indices()
+        .map(|(i, c)| cell(c).ok_or_else(|| ParseError::at(input, &line[i..], expected)))
+        .collect()
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).expect("position outside the grid")
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos).expect("position outside the grid")
+    }
+}
+
+fn write_rows<'a, T: fmt::Display + 'a, R: Iterator<Item = &'a T>>(
+    f: &mut fmt::Formatter,
+    rows: impl Iterator<Item = R>,
+) -> fmt::Result {
+    for (i, row) in rows.enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        for cell in row {
+            write!(f, "{}", cell)?;
+        }
+    }
+    Ok(())
+}
+
+/// Shows the grid a row per line, without a line break after the last.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+/// A rectangular part of a `Grid`, with positions relative to its own top
+/// left corner.
+#[derive(Clone, Copy, Debug)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Pos,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&'a T> {
+        if x < self.width && y < self.height {
+            self.grid.get((self.origin.0 + x, self.origin.1 + y))
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> slice::Iter<'a, T> {
+        // Past the last row, the slice could still be inside the grid.
+        assert!(y < self.height, "row {} of a view {} high", y, self.height);
+        let start = (self.origin.1 + y) * self.grid.width + self.origin.0;
+        self.grid.cells[start..start + self.width].iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = slice::Iter<'a, T>> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.rows().flatten()
+    }
+}
+
+impl<'a, T: fmt::Display> fmt::Display for View<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        let parse = |input| Grid::parse(input, "a digit", |c| c.to_digit(10));
+        assert_eq!(
+            parse("123\n4x6\n").unwrap_err(),
+            ParseError::new(2, 2, "a digit")
+        );
+        assert_eq!(
+            parse("123\n45\n").unwrap_err(),
+            ParseError::new(2, 1, "a row 3 wide")
+        );
+        assert_eq!(
+            parse("").unwrap_err(),
+            ParseError::new(1, 1, "at least one row")
+        );
+
+        let padded = Grid::parse_padded(" #\n#\n", "a tile", ' ', Some).unwrap();
+        assert_eq!(padded.to_string(), " #\n# ");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789\n");
+        let values = |ps: Vec<Pos>| ps.into_iter().map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(values(grid.neighbours4((1, 1)).collect()), [2, 4, 6, 8]);
+        assert_eq!(values(grid.neighbours4((0, 0)).collect()), [2, 4]);
+        assert_eq!(
+            values(grid.neighbours8((1, 1)).collect()),
+            [1, 2, 3, 4, 6, 7, 8, 9]
+        );
+        assert_eq!(values(grid.neighbours8((2, 2)).collect()), [5, 6, 8]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = digits("123\n456\n");
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), [4, 5, 6]);
+        assert_eq!(grid.column(2).rev().copied().collect::<Vec<_>>(), [6, 3]);
+        assert_eq!(grid.columns().count(), 3);
+        grid.column_mut(1).for_each(|c| *c = 0);
+        assert_eq!(grid.to_string(), "103\n406");
+        assert_eq!(grid.position(|&c| c == 6), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = digits("123\n456\n");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_right().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_left().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+
+        let empty: Grid<u8> = Grid::from_fn(3, 0, |_| 0);
+        assert_eq!(empty.rotate_right(), Grid::from_fn(0, 3, |_| 0));
+        assert_eq!(empty.rotate_left(), Grid::from_fn(0, 3, |_| 0));
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = digits("1234\n5678\n9012\n");
+        let view = grid.view((1, 1), 2, 5);
+        assert_eq!((view.width(), view.height()), (2, 2));
+        assert_eq!(view.get((1, 0)), Some(&7));
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.to_string(), "67\n01");
+        assert_eq!(view.iter().sum::<u32>(), 14);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 of a grid 3 wide")]
+    fn test_column_out_of_bounds() {
+        let _ = digits("123\n456\n").column(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 of a view 2 high")]
+    fn test_view_row_out_of_bounds() {
+        let _ = digits("1234\n5678\n9012\n").view((1, 0), 2, 2).row(2);
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str;
+
+use memmap2::Mmap;
+
+enum Text {
+    Owned(String),
+    Mapped(Mmap),
+}
+
+/// A puzzle input, from wherever it came from, with `\n` line endings and a
+/// final newline (unless it is empty), so that no day has to cope with
+/// either being different.
+pub struct Input {
+    text: Text,
+}
+
+impl Input {
+    pub fn from_stdin() -> io::Result<Self> {
+        let mut text = String::new();
+        io::stdin().lock().read_to_string(&mut text)?;
+        Ok(Input::from(text))
+    }
+
+    pub fn from_path(path: &Path) -> io::Result<Self> {
+        Ok(Input::from(fs::read_to_string(path)?))
+    }
+
+    /// Maps the file at `path` into memory rather than reading it, falling
+    /// back to a copy only if it needs its line endings fixed.
+    pub fn mmap(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the map is only ever read, and the inputs are not
+        // modified while a day runs. If one were truncated underneath us
+        // the process could fault, as with any mapped file.
+        let map = unsafe { Mmap::map(&file)? };
+        let text =
+            str::from_utf8(&map).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if is_normalized(text) {
+            Ok(Input {
+                text: Text::Mapped(map),
+            })
+        } else {
+            Ok(Input::from(text))
+        }
+    }
+
+    /// Reads the input at `path`, or stdin if the path is `-`.
+    pub fn from_arg(path: &Path) -> io::Result<Self> {
+        if path == Path::new("-") {
+            Input::from_stdin()
+        } else {
+            Input::from_path(path)
+        }
+    }
+
+    /// Reads the input named by a `--input <path>` argument in `args`, or
+    /// stdin if there is none or the path is `-`.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> io::Result<Self> {
+        let (path, rest) = input_arg(args, USAGE)?;
+        if let Some(arg) = rest.first() {
+            return Err(usage_error(format!("unexpected argument: {}", arg), USAGE));
+        }
+        Input::from_arg(&path)
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.text {
+            Text::Owned(text) => text,
+            // Checked when the file was mapped.
+            Text::Mapped(map) => unsafe { str::from_utf8_unchecked(map) },
+        }
+    }
+}
+
+/// Opens `path` to be read a line at a time rather than all at once, or
+/// stdin if the path is `-`. Lines come out the same as from an `Input`,
+/// since `lines` drops `\r\n` endings too.
+pub fn open(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+fn is_normalized(text: &str) -> bool {
+    !text.contains("\r\n") && (text.is_empty() || text.ends_with('\n'))
+}
+
+fn normalize(text: &str) -> String {
+    let mut normalized = text.replace("\r\n", "\n");
+    if !normalized.is_empty() && !normalized.ends_with('\n') {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+const USAGE: &str = "usage: [--input <path>]";
+
+/// The error for a bad command line argument, with the `usage` line of the
+/// binary it was given to.
+pub fn usage_error(message: impl fmt::Display, usage: &str) -> io::Error {
+    let message = format!("{} ({})", message, usage);
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// Picks a `--input <path>` or `--input=<path>` argument out of `args`,
+/// returning the path, `-` for stdin if there is none, along with the other
+/// arguments in order, for a binary that takes more to make sense of.
+pub fn input_arg(
+    args: impl IntoIterator<Item = String>,
+    usage: &str,
+) -> io::Result<(PathBuf, Vec<String>)> {
+    let (path, rest) = optional_input_arg(args, usage)?;
+    Ok((path.unwrap_or_else(|| PathBuf::from("-")), rest))
+}
+
+/// Like `input_arg`, but leaves it to the caller what to read when there is
+/// no `--input`.
+pub fn optional_input_arg(
+    args: impl IntoIterator<Item = String>,
+    usage: &str,
+) -> io::Result<(Option<PathBuf>, Vec<String>)> {
+    let mut path = None;
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| usage_error("--input needs a path", usage))?;
+                path = Some(PathBuf::from(value));
+            }
+            _ => match arg.strip_prefix("--input=") {
+                Some(value) => path = Some(PathBuf::from(value)),
+                None => rest.push(arg),
+            },
+        }
+    }
+    Ok((path, rest))
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        let text = if is_normalized(&text) {
+            text
+        } else {
+            normalize(&text)
+        };
+        Input {
+            text: Text::Owned(text),
+        }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Input {
+            text: Text::Owned(normalize(text)),
+        }
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|&a| String::from(a)).collect()
+    }
+
+    #[test]
+    fn test_normalized() {
+        assert_eq!(&*Input::from("1\r\n2\r\n"), "1\n2\n");
+        assert_eq!(&*Input::from("1\n2"), "1\n2\n");
+        assert_eq!(&*Input::from(String::from("1\n")), "1\n");
+        assert_eq!(&*Input::from(""), "");
+        // A lone `\r` is not a line ending, so it is left alone either way.
+        assert!(is_normalized("a\rb\n"));
+        assert_eq!(&*Input::from("a\rb\n"), "a\rb\n");
+        assert_eq!(&*Input::from(String::from("a\rb\n")), "a\rb\n");
+    }
+
+    #[test]
+    fn test_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (clean, crlf) = (dir.join("clean.txt"), dir.join("crlf.txt"));
+        fs::write(&clean, "a\nb\n").unwrap();
+        fs::write(&crlf, "a\r\nb").unwrap();
+        for path in [&clean, &crlf] {
+            assert_eq!(&*Input::mmap(path).unwrap(), "a\nb\n");
+            assert_eq!(&*Input::from_path(path).unwrap(), "a\nb\n");
+        }
+        assert!(Input::mmap(&dir.join("missing.txt")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_input_arg() {
+        let input_arg = |list| input_arg(args(list), USAGE);
+        assert_eq!(input_arg(&[]).unwrap(), (PathBuf::from("-"), vec![]));
+        assert_eq!(
+            input_arg(&["--input", "a.txt"]).unwrap(),
+            (PathBuf::from("a.txt"), vec![])
+        );
+        assert_eq!(
+            input_arg(&["--top", "3", "--input=b.txt"]).unwrap(),
+            (PathBuf::from("b.txt"), args(&["--top", "3"]))
+        );
+        assert!(input_arg(&["--input"]).is_err());
+        assert_eq!(
+            optional_input_arg(args(&["1", "--part", "2"]), USAGE).unwrap(),
+            (None, args(&["1", "--part", "2"]))
+        );
+        assert!(Input::from_args(args(&["a.txt"])).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+use std::ops::{Add, Range, RangeInclusive, Sub};
+
+/// The integer types an `IntervalSet` can hold.
+pub trait Coord: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A set of integers, stored as sorted, disjoint half-open intervals. Two
+/// intervals that touch are merged, so every set has exactly one
+/// representation.
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Range<T>>,
+}
+
+impl<T: Coord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Sorts `intervals` and merges those that overlap or touch, dropping
+    /// empty ones.
+    fn normalize(mut intervals: Vec<Range<T>>) -> Self {
+        intervals.retain(|r| r.start < r.end);
+        intervals.sort_unstable_by_key(|r| r.start);
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(intervals.len());
+        for r in intervals {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |total, r| total + (r.end - r.start))
+    }
+
+    /// The intervals making up the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.intervals.iter().cloned()
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|r| r.end <= x);
+        self.intervals.get(i).is_some_and(|r| r.start <= x)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&IntervalSet::from(range));
+    }
+
+    /// Takes the integers in `range` out of the set.
+    pub fn remove(&mut self, range: Range<T>) {
+        *self = self.difference(&IntervalSet::from(range));
+    }
+
+    pub fn remove_point(&mut self, x: T) {
+        self.remove(x..x + T::ONE);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.iter().chain(other.iter()).collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                result.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// The integers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = Vec::new();
+        let mut j = 0;
+        for a in self.intervals.iter() {
+            let mut start = a.start;
+            // Skip past the intervals of `other` that end before this one
+            // starts; they cannot affect any later interval either.
+            while other.intervals.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(b) = other.intervals.get(k).filter(|b| b.start < a.end) {
+                if start < b.start {
+                    result.push(start..b.start);
+                }
+                start = start.max(b.end);
+                k += 1;
+            }
+            if start < a.end {
+                result.push(start..a.end);
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// The integers within `bounds` that are not in the set.
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        IntervalSet::from(bounds).difference(self)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+}
+
+impl<T: Coord> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Coord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::normalize(vec![range])
+    }
+}
+
+/// The error for an inclusive range ending at the largest value of its type,
+/// which a half-open interval cannot hold.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EndsAtMax;
+
+impl fmt::Display for EndsAtMax {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the range ends at the largest value of its type")
+    }
+}
+
+impl<T: Coord> TryFrom<RangeInclusive<T>> for IntervalSet<T> {
+    type Error = EndsAtMax;
+
+    fn try_from(range: RangeInclusive<T>) -> Result<Self, EndsAtMax> {
+        let (start, end) = range.into_inner();
+        let end = end.checked_add(T::ONE).ok_or(EndsAtMax)?;
+        Ok(Self::normalize(vec![start..end]))
+    }
+}
+
+impl<T: Coord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.intervals.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeSet;
+
+    use crate::generate::Rng;
+
+    fn set(intervals: &[Range<i64>]) -> IntervalSet<i64> {
+        intervals.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_normalized() {
+        let s = set(&[5..8, 0..2, 2..3, 7..10, 4..4]);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![0..3, 5..10]);
+        assert_eq!(s.len(), 8);
+        assert!(s.contains(2) && !s.contains(3) && s.contains(9) && !s.contains(10));
+        assert_eq!(IntervalSet::try_from(1..=3), Ok(IntervalSet::from(1..4)));
+        assert_eq!(
+            IntervalSet::try_from(7..=u32::MAX - 1),
+            Ok(IntervalSet::from(7..u32::MAX))
+        );
+        assert_eq!(IntervalSet::try_from(7..=u32::MAX), Err(EndsAtMax));
+    }
+
+    #[test]
+    fn test_operations() {
+        let (a, b) = (set(&[0..5, 10..15]), IntervalSet::from(3..12));
+        assert_eq!(a.union(&b), IntervalSet::from(0..15));
+        assert_eq!(a.intersection(&b), set(&[3..5, 10..12]));
+        assert_eq!(a.difference(&b), set(&[0..3, 12..15]));
+        assert_eq!(a.complement(-5..20), set(&[-5..0, 5..10, 15..20]));
+
+        let mut c = a.clone();
+        c.remove_point(2);
+        c.remove(12..20);
+        assert_eq!(c, set(&[0..2, 3..5, 10..12]));
+        assert!(c.is_subset(&a) && !a.is_subset(&c));
+        assert!(IntervalSet::from(5..10).is_disjoint(&a));
+    }
+
+    #[test]
+    fn test_random_sets() {
+        let mut rng = Rng::new(13);
+        let mut random = || {
+            let intervals: Vec<Range<i64>> = (0..rng.range(0, 6))
+                .map(|_| {
+                    let start = rng.range(-20, 20);
+                    start..start + rng.range(0, 8)
+                })
+                .collect();
+            let points: BTreeSet<i64> = intervals.iter().cloned().flatten().collect();
+            (intervals.into_iter().collect::<IntervalSet<i64>>(), points)
+        };
+        let points = |s: &IntervalSet<i64>| s.iter().flatten().collect::<BTreeSet<i64>>();
+        for _ in 0..200 {
+            let ((a, p), (b, q)) = (random(), random());
+            assert_eq!(a.len() as usize, p.len());
+            assert_eq!(points(&a.union(&b)), &p | &q);
+            assert_eq!(points(&a.intersection(&b)), &p & &q);
+            assert_eq!(points(&a.difference(&b)), &p - &q);
+            assert_eq!(a.is_subset(&b), p.is_subset(&q));
+        }
+    }
+}
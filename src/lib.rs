@@ -0,0 +1,63 @@
+use std::fmt;
+
+use crate::error::ParseError;
+
+pub mod answers;
+pub mod bench;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day18;
+pub mod day20;
+pub mod day22;
+pub mod day23;
+pub mod day25;
+pub mod error;
+pub mod fetch;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod memory;
+pub mod parsers;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod trace;
+pub mod visualize;
+
+/// A single day's puzzle: parse the input once, then answer both parts from
+/// the parsed representation.
+pub trait Solution {
+    type Parsed;
+    type PartOne: fmt::Display;
+    type PartTwo: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne;
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo;
+}
+
+/// The answer for a part that has no puzzle, such as the second part of day 25.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NoAnswer;
+
+impl fmt::Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "-")
+    }
+}
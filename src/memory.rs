@@ -0,0 +1,214 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Whether allocations are being counted: only when built with the
+/// `alloc-stats` feature, so that normal builds keep the system allocator
+/// untouched.
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// Counters are kept per thread, so that days run in parallel do not count
+// each other's allocations.
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    // How many measurements this thread is inside of.
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+// Resident memory belongs to the whole process, so only one thread at a time
+// may measure it, or each would see the others' memory as its own.
+static RESIDENT: Mutex<()> = Mutex::new(());
+
+/// Where Linux reports the process's peak resident memory, as `VmHWM`.
+const STATUS_PATH: &str = "/proc/self/status";
+/// Writing `5` here resets that peak to what is resident now.
+const CLEAR_REFS_PATH: &str = "/proc/self/clear_refs";
+
+/// The system allocator, counting what it hands out.
+pub struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    // `try_with` rather than `with`: the counters may already be gone while
+    // a thread is being torn down.
+    let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+    let _ = BYTES.try_with(|n| n.set(n.get() + size as u64));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record_alloc(new_size);
+        }
+        new
+    }
+}
+
+/// What a piece of code allocated on its own thread, and the most memory the
+/// process had resident while it ran.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// The peak resident set size of the process while the code ran, if the
+    /// system reports it (only Linux does), and this is not nested inside
+    /// another measurement. Measurements take turns across threads, so it is
+    /// not inflated by other days running alongside.
+    pub peak_resident_bytes: Option<u64>,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} allocations, {} bytes", self.allocations, self.bytes)?;
+        match self.peak_resident_bytes {
+            Some(peak) => write!(f, ", {} bytes peak resident", peak),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Runs `f`, counting its allocations if counting is enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if ENABLED {
+        let (result, stats) = count(f);
+        (result, Some(stats))
+    } else {
+        (f(), None)
+    }
+}
+
+/// Resets the process's peak resident memory, returning whether it could.
+fn reset_peak_resident() -> bool {
+    fs::write(CLEAR_REFS_PATH, "5").is_ok()
+}
+
+/// The process's peak resident memory since it started or was last reset.
+fn peak_resident() -> Option<u64> {
+    let status = fs::read_to_string(STATUS_PATH).ok()?;
+    let line = status.lines().find_map(|l| l.strip_prefix("VmHWM:"))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// A measurement in progress on this thread. Only the outermost one takes
+/// the resident memory lock and resets the peak, so only it reports one.
+struct Section {
+    _resident: Option<MutexGuard<'static, ()>>,
+    reset: bool,
+}
+
+impl Section {
+    fn enter() -> Self {
+        let depth = DEPTH.with(|d| d.replace(d.get() + 1));
+        if depth > 0 {
+            return Section {
+                _resident: None,
+                reset: false,
+            };
+        }
+        // A part that panicked while measuring poisons the lock, but leaves
+        // nothing behind that the next measurement needs.
+        let lock = RESIDENT.lock().unwrap_or_else(PoisonError::into_inner);
+        Section {
+            _resident: Some(lock),
+            reset: reset_peak_resident(),
+        }
+    }
+}
+
+impl Drop for Section {
+    fn drop(&mut self) {
+        DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+fn count<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let section = Section::enter();
+    let allocations = ALLOCATIONS.with(Cell::get);
+    let bytes = BYTES.with(Cell::get);
+
+    let result = f();
+
+    let allocations = ALLOCATIONS.with(Cell::get) - allocations;
+    let bytes = BYTES.with(Cell::get) - bytes;
+    let peak_resident_bytes = if section.reset { peak_resident() } else { None };
+    let stats = AllocStats {
+        allocations,
+        bytes,
+        peak_resident_bytes,
+    };
+    (result, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let small = Layout::from_size_align(100, 8).unwrap();
+        let large = Layout::from_size_align(1000, 8).unwrap();
+        let (_, stats) = count(|| unsafe {
+            let a = CountingAllocator.alloc(small);
+            CountingAllocator.dealloc(a, small);
+            let b = CountingAllocator.alloc(large);
+            let b = CountingAllocator.realloc(b, large, 500);
+            CountingAllocator.dealloc(b, Layout::from_size_align(500, 8).unwrap());
+        });
+        // Whatever the real allocator does alongside is counted too, when
+        // it is installed, so only check for at least ours.
+        assert!(stats.allocations >= 3);
+        assert!(stats.bytes >= 1600);
+        if !ENABLED {
+            assert_eq!((stats.allocations, stats.bytes), (3, 1600));
+        }
+    }
+
+    #[test]
+    fn test_peak_resident() {
+        const SIZE: usize = 64 << 20;
+        let (sum, stats) = count(|| {
+            // Touch every page, so that all of it becomes resident.
+            let block = vec![1u8; SIZE];
+            block
+                .iter()
+                .step_by(4096)
+                .map(|&b| b as usize)
+                .sum::<usize>()
+        });
+        assert_eq!(sum, SIZE / 4096);
+        if let Some(peak) = stats.peak_resident_bytes {
+            assert!(peak >= SIZE as u64, "peak of {} bytes", peak);
+        }
+        if cfg!(target_os = "linux") {
+            assert!(peak_resident().is_some());
+        }
+    }
+}
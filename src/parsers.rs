@@ -0,0 +1,366 @@
+use std::str::FromStr;
+
+use nom::character::complete::line_ending;
+use nom::error::{ContextError, ErrorKind, FromExternalError};
+
+use crate::error::ParseError;
+use crate::grid::Grid;
+
+/// The error type threaded through the nom parsers: the input remaining where
+/// parsing failed, and a description of what was expected there.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NomError<'a> {
+    pub input: &'a str,
+    pub expected: String,
+}
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, NomError<'a>>;
+
+impl<'a> NomError<'a> {
+    pub fn new(input: &'a str, expected: impl Into<String>) -> Self {
+        NomError {
+            input,
+            expected: expected.into(),
+        }
+    }
+
+    /// Converts to a `ParseError` with a line and column relative to `input`,
+    /// which must contain the slice this error points at.
+    pub fn locate(self, input: &str) -> ParseError {
+        ParseError::at(input, self.input, self.expected)
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    let description = match kind {
+        ErrorKind::Digit => "a digit",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Eof => "end of input",
+        ErrorKind::MapRes => "a number in range",
+        ErrorKind::Verify => "a valid value",
+        ErrorKind::Char | ErrorKind::OneOf | ErrorKind::NoneOf => "a valid character",
+        _ => return format!("{:?}", kind),
+    };
+    String::from(description)
+}
+
+impl<'a> nom::error::ParseError<&'a str> for NomError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        NomError::new(input, describe(kind))
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        NomError::new(input, format!("{:?}", c))
+    }
+
+    // Keep whichever alternative got furthest; if they failed at the same
+    // spot, either would have done.
+    fn or(self, other: Self) -> Self {
+        match self.input.len().cmp(&other.input.len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                let expected = format!("{} or {}", self.expected, other.expected);
+                NomError::new(self.input, expected)
+            }
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for NomError<'a> {
+    fn add_context(input: &'a str, context: &'static str, other: Self) -> Self {
+        if other.input.len() == input.len() {
+            NomError::new(input, context)
+        } else {
+            other
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for NomError<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _: E) -> Self {
+        NomError::new(input, describe(kind))
+    }
+}
+
+/// Like nom's `tag`, but reports the missing text when it fails.
+pub fn tag<'a>(t: &'static str) -> impl Fn(&'a str) -> IResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(t) {
+        Some(rest) => Ok((rest, &input[..t.len()])),
+        None => Err(nom::Err::Error(NomError::new(input, format!("{:?}", t)))),
+    }
+}
+
+/// Parses an unsigned integer of any width, failing rather than wrapping if
+/// it does not fit.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<'_, T> {
+    number(input, 0)
+}
+
+/// Parses an integer of any width, with an optional leading `-`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<'_, T> {
+    number(input, usize::from(input.starts_with('-')))
+}
+
+/// Parses the digits following the first `sign` bytes of `input`, sign
+/// included, as a `T`.
+fn number<T: FromStr>(input: &str, sign: usize) -> IResult<'_, T> {
+    let digits = input[sign..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len() - sign);
+    if digits == 0 {
+        return Err(nom::Err::Error(NomError::new(&input[sign..], "a digit")));
+    }
+    let (number, rest) = input.split_at(sign + digits);
+    match number.parse() {
+        Ok(n) => Ok((rest, n)),
+        Err(_) => Err(nom::Err::Error(NomError::new(input, "a number in range"))),
+    }
+}
+
+/// Parses one `element` per line. Unlike `separated_list1`, an element that
+/// fails after a line ending is an error rather than the end of the list.
+pub fn lines<'a, O>(
+    mut element: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    move |mut input: &'a str| {
+        let mut result = Vec::new();
+        loop {
+            let (rest, output) = element(input)?;
+            result.push(output);
+            if rest.is_empty() {
+                return Ok((rest, result));
+            }
+            let (rest, _) = line_ending::<_, NomError>(rest)
+                .map_err(|_| nom::Err::Error(NomError::new(rest, "end of line")))?;
+            if rest.is_empty() {
+                return Ok((rest, result));
+            }
+            input = rest;
+        }
+    }
+}
+
+/// Parses `element`s split by `separator`. A separator followed by anything
+/// other than the end of input commits to parsing another element: if that
+/// fails, the error is a `Failure` so that no enclosing `alt` backtracks
+/// past it.
+pub fn separated<'a, O, S>(
+    mut separator: impl FnMut(&'a str) -> IResult<'a, S>,
+    mut element: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    move |input: &'a str| {
+        let (mut input, first) = element(input)?;
+        let mut result = vec![first];
+        loop {
+            let rest = match separator(input) {
+                Ok((rest, _)) if !rest.is_empty() => rest,
+                Ok(_) | Err(nom::Err::Error(_)) => return Ok((input, result)),
+                Err(e) => return Err(e),
+            };
+            let (rest, output) = element(rest).map_err(|e| match e {
+                nom::Err::Error(e) => nom::Err::Failure(e),
+                e => e,
+            })?;
+            result.push(output);
+            input = rest;
+        }
+    }
+}
+
+/// Parses blocks of lines separated by blank lines, running `block` over
+/// each block on its own. Consumes the rest of the input.
+pub fn blocks<'a, O>(
+    mut block: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    move |input: &'a str| {
+        let mut result = Vec::new();
+        let mut rest = input;
+        loop {
+            let (this, next) = split_block(rest);
+            let (_, output) = whole(&mut block)(this)?;
+            result.push(output);
+            match next {
+                Some(next) if !next.is_empty() => rest = next,
+                _ => return Ok((&input[input.len()..], result)),
+            }
+        }
+    }
+}
+
+/// Splits `input` at its first blank line, into what comes before and, if
+/// there is a blank line, what comes after.
+fn split_block(input: &str) -> (&str, Option<&str>) {
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']).is_empty() {
+            return (&input[..offset], Some(&input[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    (input, None)
+}
+
+/// Parses a map of characters, one row per line, converting each with
+/// `cell`, up to a blank line or the end of the input. Every row must be as
+/// wide as the first.
+pub fn grid<'a, 'e, T>(
+    expected: &'e str,
+    mut cell: impl FnMut(char) -> Option<T> + 'e,
+) -> impl FnMut(&'a str) -> IResult<'a, Grid<T>> + 'e {
+    move |input: &'a str| {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        let mut rest = input;
+        loop {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            let line = rest[..len].trim_end_matches('\r');
+            if line.is_empty() {
+                break;
+            }
+            let mut row = Vec::new();
+            for (i, c) in line.char_indices() {
+                let t =
+                    cell(c).ok_or_else(|| nom::Err::Error(NomError::new(&line[i..], expected)))?;
+                row.push(t);
+            }
+            if let Some(first) = rows.first().map(Vec::len) {
+                if row.len() != first {
+                    let expected = format!("a row {} wide", first);
+                    return Err(nom::Err::Error(NomError::new(line, expected)));
+                }
+            }
+            rows.push(row);
+            rest = &rest[(len + 1).min(rest.len())..];
+        }
+        if rows.is_empty() {
+            return Err(nom::Err::Error(NomError::new(rest, "at least one row")));
+        }
+        Ok((rest, Grid::from_rows(rows).unwrap()))
+    }
+}
+
+/// Runs `parser`, then requires that nothing but line endings is left.
+pub fn whole<'a, O>(
+    mut parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, O> {
+    move |input: &'a str| {
+        let (rest, output) = parser(input)?;
+        let end = rest.trim_start_matches(['\r', '\n']);
+        if end.is_empty() {
+            Ok((end, output))
+        } else {
+            Err(nom::Err::Error(NomError::new(rest, "end of input")))
+        }
+    }
+}
+
+/// Runs `parser` over all of `input`, allowing nothing but line endings to
+/// be left over.
+pub fn complete<'a, O>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> Result<O, NomError<'a>> {
+    match whole(parser)(input) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(e),
+        Err(nom::Err::Incomplete(_)) => Err(NomError::new(&input[input.len()..], "more input")),
+    }
+}
+
+/// Like `complete`, with the error located within `input`.
+pub fn finish<'a, O>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> Result<O, ParseError> {
+    complete(input, parser).map_err(|e| e.locate(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::branch::alt;
+    use nom::sequence::separated_pair;
+
+    fn pair(input: &str) -> IResult<'_, (u32, u32)> {
+        separated_pair(unsigned, tag("-"), unsigned)(input)
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(finish("1-2\n3-4\n", lines(pair)), Ok(vec![(1, 2), (3, 4)]));
+        assert_eq!(
+            finish("1-2\n3+4\n", lines(pair)),
+            Err(ParseError::new(2, 2, "\"-\""))
+        );
+        assert_eq!(
+            finish("1-2x\n", lines(pair)),
+            Err(ParseError::new(1, 4, "end of line"))
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(finish("255", unsigned::<u8>), Ok(255));
+        assert_eq!(
+            finish("256", unsigned::<u8>),
+            Err(ParseError::new(1, 1, "a number in range"))
+        );
+        assert_eq!(finish("-128", signed::<i8>), Ok(-128));
+        assert_eq!(finish("-9000000000", signed::<i64>), Ok(-9000000000));
+        assert_eq!(
+            finish("-", signed::<i64>),
+            Err(ParseError::new(1, 2, "a digit"))
+        );
+        assert_eq!(
+            finish("-1", unsigned::<u32>),
+            Err(ParseError::new(1, 1, "a digit"))
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "1-2\n3-4\n\n5-6\n";
+        assert_eq!(
+            finish(input, blocks(lines(pair))),
+            Ok(vec![vec![(1, 2), (3, 4)], vec![(5, 6)]])
+        );
+        assert_eq!(
+            finish("1-2\n\n5-6x\n", blocks(lines(pair))),
+            Err(ParseError::new(3, 4, "end of line"))
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let digit = |c: char| c.to_digit(10);
+        let g = finish("12\n34\n", grid("a digit", digit)).unwrap();
+        assert_eq!((g.width(), g.height(), g[(1, 1)]), (2, 2, 4));
+        assert_eq!(
+            finish("12\n3x\n", grid("a digit", digit)),
+            Err(ParseError::new(2, 2, "a digit"))
+        );
+        assert_eq!(
+            finish("12\n345\n", grid("a digit", digit)),
+            Err(ParseError::new(2, 1, "a row 2 wide"))
+        );
+        assert_eq!(
+            finish("12\n\n34\n", grid("a digit", digit)),
+            Err(ParseError::new(2, 1, "end of input"))
+        );
+    }
+
+    #[test]
+    fn test_alt() {
+        let mut ab = alt((tag("a"), tag("b")));
+        assert_eq!(
+            finish("c", &mut ab),
+            Err(ParseError::new(1, 1, "\"a\" or \"b\""))
+        );
+    }
+}
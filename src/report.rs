@@ -0,0 +1,174 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::runner::DayReport;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("invalid format: {}", s)),
+        }
+    }
+}
+
+/// One answered part, flattened for machine-readable output. The parse time
+/// is that of the day's input, so it repeats for both parts of a day.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a str,
+    pub parse_ns: u128,
+    pub solve_ns: u128,
+}
+
+pub fn records(report: &DayReport) -> impl Iterator<Item = Record<'_>> {
+    report.parts.iter().map(|p| Record {
+        day: report.day,
+        part: p.part.number(),
+        answer: &p.answer,
+        parse_ns: report.parse_time.as_nanos(),
+        solve_ns: p.elapsed.as_nanos(),
+    })
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(result, "\\u{:04x}", c as u32);
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Quotes a CSV field if it needs it, as described by RFC 4180: fields with
+/// commas, quotes or line breaks are wrapped in quotes, with quotes doubled.
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        String::from(s)
+    }
+}
+
+/// Writes a JSON array with one record object per line. Multi-line answers
+/// stay a single string, with `\n` between the lines.
+pub fn write_json<'a>(
+    out: &mut impl Write,
+    records: impl IntoIterator<Item = Record<'a>>,
+) -> io::Result<()> {
+    let mut separator = "[";
+    for r in records {
+        writeln!(
+            out,
+            "{}{{\"day\":{},\"part\":{},\"answer\":{},\"parse_ns\":{},\"solve_ns\":{}}}",
+            separator,
+            r.day,
+            r.part,
+            json_string(r.answer),
+            r.parse_ns,
+            r.solve_ns
+        )?;
+        separator = ",";
+    }
+    if separator == "[" {
+        write!(out, "[")?;
+    }
+    writeln!(out, "]")
+}
+
+/// Writes a header row and one row per record. Multi-line answers are quoted,
+/// keeping their line breaks inside the field.
+pub fn write_csv<'a>(
+    out: &mut impl Write,
+    records: impl IntoIterator<Item = Record<'a>>,
+) -> io::Result<()> {
+    writeln!(out, "day,part,answer,parse_ns,solve_ns")?;
+    for r in records {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            r.day,
+            r.part,
+            csv_field(r.answer),
+            r.parse_ns,
+            r.solve_ns
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Record<'static>> {
+        vec![
+            Record {
+                day: 1,
+                part: 1,
+                answer: "66616",
+                parse_ns: 10,
+                solve_ns: 20,
+            },
+            Record {
+                day: 10,
+                part: 2,
+                answer: "#  \"#\"\n, #",
+                parse_ns: 30,
+                solve_ns: 40,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut out = Vec::new();
+        write_json(&mut out, sample()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[{\"day\":1,\"part\":1,\"answer\":\"66616\",\"parse_ns\":10,\"solve_ns\":20}\n\
+             ,{\"day\":10,\"part\":2,\"answer\":\"#  \\\"#\\\"\\n, #\",\"parse_ns\":30,\"solve_ns\":40}\n\
+             ]\n"
+        );
+
+        let mut out = Vec::new();
+        write_json(&mut out, Vec::new()).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "[]\n");
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut out = Vec::new();
+        write_csv(&mut out, sample()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day,part,answer,parse_ns,solve_ns\n\
+             1,1,66616,10,20\n\
+             10,2,\"#  \"\"#\"\"\n, #\",30,40\n"
+        );
+    }
+}
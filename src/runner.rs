@@ -0,0 +1,286 @@
+use std::any::Any;
+use std::fmt;
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::bench::{BenchReport, Stage, Stats};
+use crate::error::ParseError;
+use crate::memory::{self, AllocStats};
+use crate::Solution;
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day18, day20, day22, day23, day25,
+};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(n: u8) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: String,
+    pub elapsed: Duration,
+    /// What the part allocated, if built with allocation counting.
+    pub alloc: Option<AllocStats>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub day: u8,
+    pub parse_time: Duration,
+    pub parse_alloc: Option<AllocStats>,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.elapsed).sum::<Duration>()
+    }
+}
+
+/// How a day went as part of a batch: its report, or what went wrong, and
+/// how long it took from start to finish.
+#[derive(Debug)]
+pub struct DayOutcome {
+    pub day: u8,
+    pub result: Result<DayReport, String>,
+    pub wall_time: Duration,
+}
+
+/// How parsing went: its time and allocations.
+type ParseStats = (Duration, Option<AllocStats>);
+type SolveFn = fn(&str, &[Part]) -> Result<(ParseStats, Vec<PartReport>), ParseError>;
+type BenchFn = fn(&str, usize) -> Result<[Vec<Duration>; 3], ParseError>;
+
+/// A registered day, with its solution erased behind function pointers so
+/// that days with different answer types can live in one table.
+pub struct Day {
+    pub number: u8,
+    solve: SolveFn,
+    bench: BenchFn,
+}
+
+impl Day {
+    const fn of<S: Solution>(number: u8) -> Day {
+        Day {
+            number,
+            solve: solve::<S>,
+            bench: bench::<S>,
+        }
+    }
+
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<DayReport, ParseError> {
+        let ((parse_time, parse_alloc), parts) = (self.solve)(input, parts)?;
+        Ok(DayReport {
+            day: self.number,
+            parse_time,
+            parse_alloc,
+            parts,
+        })
+    }
+
+    /// Times parsing and both parts `runs` times over, each run starting
+    /// from a fresh parse.
+    pub fn bench(&self, input: &str, runs: usize) -> Result<BenchReport, ParseError> {
+        let samples = (self.bench)(input, runs)?;
+        let stages = Stage::ALL
+            .into_iter()
+            .zip(samples)
+            .filter_map(|(stage, mut samples)| Some((stage, Stats::from_samples(&mut samples)?)))
+            .collect();
+        Ok(BenchReport {
+            day: self.number,
+            runs,
+            stages,
+        })
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        input_path(self.number)
+    }
+}
+
+fn solve<S: Solution>(
+    input: &str,
+    parts: &[Part],
+) -> Result<(ParseStats, Vec<PartReport>), ParseError> {
+    let start = Instant::now();
+    let (parsed, parse_alloc) = memory::measure(|| S::parse(input));
+    let parse_time = start.elapsed();
+    let parsed = parsed?;
+
+    let mut reports = Vec::new();
+    for &part in parts.iter() {
+        let start = Instant::now();
+        let (answer, alloc) = memory::measure(|| match part {
+            Part::One => S::part_one(&parsed).to_string(),
+            Part::Two => S::part_two(&parsed).to_string(),
+        });
+        let elapsed = start.elapsed();
+        reports.push(PartReport {
+            part,
+            answer,
+            elapsed,
+            alloc,
+        });
+    }
+    Ok(((parse_time, parse_alloc), reports))
+}
+
+fn bench<S: Solution>(input: &str, runs: usize) -> Result<[Vec<Duration>; 3], ParseError> {
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(input))?;
+        samples[0].push(start.elapsed());
+
+        let start = Instant::now();
+        let answer = S::part_one(black_box(&parsed));
+        samples[1].push(start.elapsed());
+        black_box(answer);
+
+        let start = Instant::now();
+        let answer = S::part_two(black_box(&parsed));
+        samples[2].push(start.elapsed());
+        black_box(answer);
+    }
+    Ok(samples)
+}
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(1),
+    Day::of::<day02::Day02>(2),
+    Day::of::<day03::Day03>(3),
+    Day::of::<day04::Day04>(4),
+    Day::of::<day05::Day05>(5),
+    Day::of::<day06::Day06>(6),
+    Day::of::<day07::Day07>(7),
+    Day::of::<day08::Day08>(8),
+    Day::of::<day09::Day09>(9),
+    Day::of::<day10::Day10>(10),
+    Day::of::<day11::Day11>(11),
+    Day::of::<day12::Day12>(12),
+    Day::of::<day13::Day13>(13),
+    Day::of::<day14::Day14>(14),
+    Day::of::<day15::Day15>(15),
+    Day::of::<day18::Day18>(18),
+    Day::of::<day20::Day20>(20),
+    Day::of::<day22::Day22>(22),
+    Day::of::<day23::Day23>(23),
+    Day::of::<day25::Day25>(25),
+];
+
+/// Where a day's puzzle input lives, whether or not the day is implemented.
+pub fn input_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("data/day{:02}.txt", day))
+}
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+/// Runs `job` for each of `days` on up to `jobs` threads, returning the
+/// outcomes in the same order as `days`. A job that panics fails its day
+/// without taking the others down with it.
+pub fn run_parallel<F>(days: &[Day], jobs: usize, job: F) -> Vec<DayOutcome>
+where
+    F: Fn(&Day) -> Result<DayReport, String> + Sync,
+{
+    let next = &AtomicUsize::new(0);
+    let job = &job;
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let start = Instant::now();
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| job(day)))
+                        .unwrap_or_else(|e| Err(format!("panicked ({})", panic_message(&*e))));
+                    let outcome = DayOutcome {
+                        day: day.number,
+                        result,
+                        wall_time: start.elapsed(),
+                    };
+                    // The receiver outlives the scope, so this cannot fail.
+                    sender.send(outcome).unwrap();
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut outcomes: Vec<DayOutcome> = receiver.into_iter().collect();
+    outcomes.sort_by_key(|o| days.iter().position(|d| d.number == o.day));
+    outcomes
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("no message")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_parallel() {
+        let outcomes = run_parallel(DAYS, 4, |day| match day.number {
+            3 => Err(String::from("missing input")),
+            5 => panic!("bad day"),
+            _ => Ok(DayReport {
+                day: day.number,
+                parse_time: Duration::ZERO,
+                parse_alloc: None,
+                parts: Vec::new(),
+            }),
+        });
+        let days: Vec<u8> = outcomes.iter().map(|o| o.day).collect();
+        let expected: Vec<u8> = DAYS.iter().map(|d| d.number).collect();
+        assert_eq!(days, expected);
+        assert_eq!(outcomes[2].result.as_ref().unwrap_err(), "missing input");
+        assert_eq!(
+            outcomes[4].result.as_ref().unwrap_err(),
+            "panicked (bad day)"
+        );
+        let ok = outcomes.iter().filter(|o| o.result.is_ok()).count();
+        assert_eq!(ok, DAYS.len() - 2);
+    }
+}
@@ -0,0 +1,374 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LIB_PATH: &str = "src/lib.rs";
+const RUNNER_PATH: &str = "src/runner.rs";
+
+/// rustfmt's default line width, which the registration edits stay within.
+const MAX_WIDTH: usize = 100;
+
+fn module_name(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+fn struct_name(day: u8) -> String {
+    format!("Day{:02}", day)
+}
+
+pub fn day_module(day: u8) -> String {
+    let template = r#"use nom::character::complete::not_line_ending;
+use nom::combinator::map;
+
+use crate::error::ParseError;
+use crate::parsers::{finish, lines, IResult};
+use crate::Solution;
+
+fn parse_line(input: &str) -> IResult<'_, String> {
+    map(not_line_ending, String::from)(input)
+}
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    type Parsed = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        finish(input, lines(parse_line))
+    }
+
+    fn part_one(parsed: &Self::Parsed) -> usize {
+        parsed.len()
+    }
+
+    fn part_two(parsed: &Self::Parsed) -> usize {
+        parsed.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../data/examples/dayNN.txt");
+
+    #[test]
+    #[ignore = "the example answers have not been filled in yet"]
+    fn test_example() {
+        let parsed = DayNN::parse(EXAMPLE).unwrap();
+        assert_eq!(DayNN::part_one(&parsed), 0);
+        assert_eq!(DayNN::part_two(&parsed), 0);
+    }
+}
+"#;
+    fill(template, day)
+}
+
+pub fn day_binary(day: u8) -> String {
+    let template = r#"use std::env;
+
+use adventofcode_2022_rust::dayNN::DayNN;
+use adventofcode_2022_rust::error::Error;
+use adventofcode_2022_rust::input::Input;
+use adventofcode_2022_rust::Solution;
+
+fn main() -> Result<(), Error> {
+    let input = Input::from_args(env::args().skip(1))?;
+    let input = DayNN::parse(&input)?;
+
+    println!("Part one: {}", DayNN::part_one(&input));
+    println!("Part two: {}", DayNN::part_two(&input));
+
+    Ok(())
+}
+"#;
+    fill(template, day)
+}
+
+fn fill(template: &str, day: u8) -> String {
+    template
+        .replace("dayNN", &module_name(day))
+        .replace("DayNN", &struct_name(day))
+}
+
+/// Parses the day number out of a name such as `day07`.
+fn day_number(name: &str) -> Option<u8> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+/// Adds `pub mod dayNN;` to `lib.rs`, keeping the day modules in order.
+pub fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let line = format!("pub mod {};", module_name(day));
+    let existing = |l: &str| {
+        l.strip_prefix("pub mod ")
+            .and_then(|l| l.strip_suffix(';'))
+            .and_then(day_number)
+    };
+    let lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&line.as_str()) {
+        return Err(format!(
+            "{} already declares {}",
+            LIB_PATH,
+            module_name(day)
+        ));
+    }
+    let last = lines
+        .iter()
+        .rposition(|l| existing(l).is_some())
+        .ok_or_else(|| format!("{} has no day modules to add to", LIB_PATH))?;
+    let at = lines
+        .iter()
+        .position(|l| existing(l).is_some_and(|d| d > day))
+        .unwrap_or(last + 1);
+    Ok(insert_line(&lines, at, &line))
+}
+
+/// Adds the day to the runner's `use crate::{...}` list and its `DAYS`
+/// table, both in order.
+pub fn register_day(runner: &str, day: u8) -> Result<String, String> {
+    let not_found = || format!("{} has no day registry to add to", RUNNER_PATH);
+    let entry = format!(
+        "    Day::of::<{}::{}>({}),",
+        module_name(day),
+        struct_name(day),
+        day
+    );
+    let existing = |l: &str| {
+        l.trim_start()
+            .strip_prefix("Day::of::<")
+            .and_then(|l| l.split("::").next())
+            .and_then(day_number)
+    };
+
+    let mut lines: Vec<String> = runner.lines().map(String::from).collect();
+    if lines.contains(&entry) {
+        return Err(format!("{} already registers day {}", RUNNER_PATH, day));
+    }
+    let last = lines
+        .iter()
+        .rposition(|l| existing(l).is_some())
+        .ok_or_else(not_found)?;
+    let at = lines
+        .iter()
+        .position(|l| existing(l).is_some_and(|d| d > day))
+        .unwrap_or(last + 1);
+    lines.insert(at, entry);
+
+    // The import is either on one line or spread over several by rustfmt;
+    // either way, gather up the names and lay them out afresh.
+    let (start, end) = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("use crate::{"))
+        .filter_map(|(start, _)| {
+            let len = lines[start..].iter().position(|l| l.ends_with("};"))?;
+            Some((start, start + len))
+        })
+        .find(|&(start, end)| lines[start..=end].iter().any(|l| l.contains("day")))
+        .ok_or_else(not_found)?;
+    let joined = lines[start..=end].join(" ");
+    let inner = joined
+        .trim_start_matches("use crate::{")
+        .trim_end_matches("};");
+    let mut names: Vec<String> = inner
+        .split(',')
+        .map(|s| String::from(s.trim()))
+        .filter(|s| !s.is_empty())
+        .collect();
+    names.push(module_name(day));
+    names.sort();
+    let import = format_import(&names);
+    lines.splice(start..=end, import);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn format_import(names: &[String]) -> Vec<String> {
+    let single = format!("use crate::{{{}}};", names.join(", "));
+    if single.len() <= MAX_WIDTH {
+        return vec![single];
+    }
+    let mut lines = vec![String::from("use crate::{")];
+    let mut current = String::from("   ");
+    for name in names {
+        if current.len() + name.len() + 2 > MAX_WIDTH {
+            lines.push(current);
+            current = String::from("   ");
+        }
+        current.push(' ');
+        current.push_str(name);
+        current.push(',');
+    }
+    lines.push(current);
+    lines.push(String::from("};"));
+    lines
+}
+
+fn insert_line(lines: &[&str], at: usize, line: &str) -> String {
+    let mut result = String::new();
+    for (i, l) in lines.iter().enumerate() {
+        if i == at {
+            result.push_str(line);
+            result.push('\n');
+        }
+        result.push_str(l);
+        result.push('\n');
+    }
+    if at >= lines.len() {
+        result.push_str(line);
+        result.push('\n');
+    }
+    result
+}
+
+/// Creates the files for a new day under `root` and registers it with the
+/// library and the runner, returning the paths it wrote. Refuses to touch
+/// anything if the day's binary or module already exists.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let module = root.join(format!("src/{}.rs", module_name(day)));
+    let binary = root.join(format!("src/bin/{}.rs", module_name(day)));
+    let example = root.join(format!("data/examples/{}.txt", module_name(day)));
+    let lib_path = root.join(LIB_PATH);
+    let runner_path = root.join(RUNNER_PATH);
+
+    for path in [&binary, &module] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    let read =
+        |path: &Path| fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e));
+    let (lib_original, runner_original) = (read(&lib_path)?, read(&runner_path)?);
+    let lib = register_module(&lib_original, day)?;
+    let runner = register_day(&runner_original, day)?;
+
+    // Every edit that can be refused has been made by now, so all that is
+    // left to go wrong is writing. The registrations go last, so that a
+    // module is never registered before it exists.
+    let mut files = vec![(module, day_module(day)), (binary, day_binary(day))];
+    if !example.exists() {
+        if let Some(dir) = example.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        files.push((example, String::new()));
+    }
+    files.push((lib_path.clone(), lib));
+    files.push((runner_path.clone(), runner));
+
+    let mut written: Vec<PathBuf> = Vec::new();
+    for (path, contents) in files {
+        if let Err(e) = fs::write(&path, contents) {
+            // Undo what was written, so that the day can be scaffolded
+            // again once the problem is fixed.
+            for done in written.iter().rev() {
+                let _ = if *done == lib_path {
+                    fs::write(done, &lib_original)
+                } else if *done == runner_path {
+                    fs::write(done, &runner_original)
+                } else {
+                    fs::remove_file(done)
+                };
+            }
+            return Err(format!("{}: {}", path.display(), e));
+        }
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_module() {
+        let lib =
+            "use std::fmt;\n\npub mod answers;\npub mod day01;\npub mod day03;\npub mod error;\n";
+        assert_eq!(
+            register_module(lib, 2).unwrap(),
+            "use std::fmt;\n\npub mod answers;\npub mod day01;\npub mod day02;\npub mod day03;\npub mod error;\n"
+        );
+        assert_eq!(
+            register_module(lib, 4).unwrap(),
+            "use std::fmt;\n\npub mod answers;\npub mod day01;\npub mod day03;\npub mod day04;\npub mod error;\n"
+        );
+        assert!(register_module(lib, 3).is_err());
+    }
+
+    #[test]
+    fn test_register_day() {
+        let runner = "\
+use crate::{day01, day03};
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(1),
+    Day::of::<day03::Day03>(3),
+];
+";
+        assert_eq!(
+            register_day(runner, 2).unwrap(),
+            "\
+use crate::{day01, day02, day03};
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(1),
+    Day::of::<day02::Day02>(2),
+    Day::of::<day03::Day03>(3),
+];
+"
+        );
+        assert!(register_day(runner, 3).is_err());
+    }
+
+    #[test]
+    fn test_scaffold_undoes_failed_writes() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        let lib = "pub mod day01;\n";
+        let runner = "use crate::{day01};\n\npub const DAYS: &[Day] = &[\n    Day::of::<day01::Day01>(1),\n];\n";
+        fs::write(root.join(LIB_PATH), lib).unwrap();
+        fs::write(root.join(RUNNER_PATH), runner).unwrap();
+
+        // With no src/bin, the binary cannot be written.
+        assert!(scaffold(&root, 2).is_err());
+        assert!(!root.join("src/day02.rs").exists());
+        assert_eq!(fs::read_to_string(root.join(LIB_PATH)).unwrap(), lib);
+        assert_eq!(fs::read_to_string(root.join(RUNNER_PATH)).unwrap(), runner);
+
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        let written = scaffold(&root, 2).unwrap();
+        assert_eq!(written.len(), 5);
+        assert!(fs::read_to_string(root.join(LIB_PATH))
+            .unwrap()
+            .contains("pub mod day02;"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_register_day_wraps_import() {
+        let runner = "\
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day18, day20, day22, day23, day25,
+};
+
+pub const DAYS: &[Day] = &[
+    Day::of::<day15::Day15>(15),
+    Day::of::<day18::Day18>(18),
+];
+";
+        let registered = register_day(runner, 16).unwrap();
+        assert!(registered.contains(
+            "use crate::{\n    \
+             day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,\n    \
+             day14, day15, day16, day18, day20, day22, day23, day25,\n};\n"
+        ));
+        assert!(registered.contains(
+            "    Day::of::<day15::Day15>(15),\n    \
+             Day::of::<day16::Day16>(16),\n    \
+             Day::of::<day18::Day18>(18),\n"
+        ));
+    }
+}
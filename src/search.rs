@@ -0,0 +1,268 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use ahash::{AHashMap, AHashSet};
+
+/// The cost of an edge, or of a path: anything that adds up and compares,
+/// with `Default` as zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+struct Visit<N, C> {
+    node: N,
+    cost: C,
+    parent: Option<usize>,
+}
+
+/// Everything a search reached, with the cheapest known way to each node,
+/// and the goal it stopped at, if any.
+pub struct Search<N, C> {
+    visits: Vec<Visit<N, C>>,
+    index: AHashMap<N, usize>,
+    goal: Option<usize>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> Search<N, C> {
+    fn new() -> Self {
+        Search {
+            visits: Vec::new(),
+            index: AHashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// Records `node` at `cost` if that is the first or a cheaper way to it,
+    /// returning its index.
+    fn relax(&mut self, node: N, cost: C, parent: Option<usize>) -> Option<usize> {
+        match self.index.get(&node) {
+            Some(&i) if self.visits[i].cost <= cost => None,
+            Some(&i) => {
+                self.visits[i].cost = cost;
+                self.visits[i].parent = parent;
+                Some(i)
+            }
+            None => {
+                let i = self.visits.len();
+                self.index.insert(node.clone(), i);
+                self.visits.push(Visit { node, cost, parent });
+                Some(i)
+            }
+        }
+    }
+
+    /// The goal the search stopped at, if it found one.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.map(|i| &self.visits[i].node)
+    }
+
+    /// The cost of the path to the goal.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.map(|i| self.visits[i].cost)
+    }
+
+    /// The path from a start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.map(|i| self.path_from(i))
+    }
+
+    /// The cost of the cheapest path the search found to `node`. Once the
+    /// search has stopped at a goal, this is only certain to be the cheapest
+    /// for nodes no further away than the goal.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.index.get(node).map(|&i| self.visits[i].cost)
+    }
+
+    /// The path from a start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.index.get(node).map(|&i| self.path_from(i))
+    }
+
+    fn path_from(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![self.visits[i].node.clone()];
+        while let Some(parent) = self.visits[i].parent {
+            path.push(self.visits[parent].node.clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Every node the search reached, in the order it first reached them.
+    pub fn reached(&self) -> impl Iterator<Item = &N> {
+        self.visits.iter().map(|v| &v.node)
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+}
+
+/// Breadth-first search from all of `starts` at once, stopping at the first
+/// node `is_goal` accepts, or once everything reachable has been reached.
+/// Costs count edges.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue: VecDeque<usize> = starts
+        .into_iter()
+        .filter_map(|start| search.relax(start, 0, None))
+        .collect();
+    while let Some(i) = queue.pop_front() {
+        if is_goal(&search.visits[i].node) {
+            search.goal = Some(i);
+            break;
+        }
+        let cost = search.visits[i].cost + 1;
+        for next in neighbours(&search.visits[i].node) {
+            if !search.contains(&next) {
+                queue.extend(search.relax(next, cost, Some(i)));
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm: the cheapest path from any of `starts` to a node
+/// `is_goal` accepts, where `neighbours` gives each neighbour with the cost
+/// of the edge to it.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search: like Dijkstra's algorithm, but guided towards the goal by
+/// `heuristic`, which must never overestimate the cost still to go.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        if let Some(i) = search.relax(start, C::default(), None) {
+            queue.push(Reverse((estimate, Reverse(C::default()), i)));
+        }
+    }
+    // Among equally promising nodes, go on from the one furthest along.
+    while let Some(Reverse((_, Reverse(cost), i))) = queue.pop() {
+        // A node is queued again each time a cheaper way to it turns up;
+        // only the cheapest of its entries is still current.
+        if cost > search.visits[i].cost {
+            continue;
+        }
+        if is_goal(&search.visits[i].node) {
+            search.goal = Some(i);
+            break;
+        }
+        for (next, step) in neighbours(&search.visits[i].node) {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic(&next);
+            if let Some(j) = search.relax(next, next_cost, Some(i)) {
+                queue.push(Reverse((estimate, Reverse(next_cost), j)));
+            }
+        }
+    }
+    search
+}
+
+/// Every node reachable from `starts`, the starts included.
+pub fn reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+) -> AHashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs(starts, neighbours, |_| false)
+        .index
+        .into_keys()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small weighted graph where the direct edge is the expensive way.
+    fn edges(&node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 3)],
+            'e' => vec![('d', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra(['a'], edges, |&n| n == 'd');
+        assert_eq!(search.goal(), Some(&'d'));
+        assert_eq!(search.goal_cost(), Some(6));
+        assert_eq!(search.path(), Some(vec!['a', 'b', 'c', 'd']));
+
+        let search = dijkstra(['a', 'e'], edges, |&n| n == 'd');
+        assert_eq!(search.path(), Some(vec!['e', 'd']));
+
+        let search = dijkstra(['a'], edges, |&n| n == 'e');
+        assert_eq!(search.goal(), None);
+        assert!(!search.contains(&'e'));
+    }
+
+    #[test]
+    fn test_astar_and_bfs() {
+        // Walk a 10 by 10 open square from one corner to the other.
+        let goal = (9i32, 9i32);
+        let neighbours = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y))
+        };
+        let manhattan = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+
+        let guided = astar(
+            [(0, 0)],
+            |p| neighbours(p).map(|n| (n, 1)),
+            manhattan,
+            |&p| p == goal,
+        );
+        let path = guided.path().unwrap();
+        assert_eq!(guided.goal_cost(), Some(18));
+        assert_eq!((path.len(), path[0], path[18]), (19, (0, 0), goal));
+        assert!(guided.reached().count() < 100);
+
+        let search = bfs([(0, 0)], neighbours, |&p| p == goal);
+        assert_eq!(search.goal_cost(), Some(18));
+        assert_eq!(search.cost(&(3, 4)), Some(7));
+
+        let all = reachable([(0, 0)], |p| neighbours(p).filter(|&(x, _)| x < 5));
+        assert_eq!(all.len(), 50);
+    }
+}
@@ -0,0 +1,218 @@
+use std::env;
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+/// How much detail an event is: enabling a level enables every level above
+/// it too.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parses a level, or `off` for none.
+fn parse_level(s: &str) -> Result<Option<Level>, String> {
+    if s == "off" {
+        return Ok(None);
+    }
+    Level::ALL
+        .into_iter()
+        .find(|l| l.name() == s)
+        .map(Some)
+        .ok_or_else(|| format!("invalid trace level: {}", s))
+}
+
+/// Which events to show: a level for everything, and levels for particular
+/// days, as in `info,day11=trace`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Option<Level>)>,
+}
+
+impl Filter {
+    fn level_for(&self, target: &str) -> Option<Level> {
+        self.targets
+            .iter()
+            .find(|(t, _)| t == target)
+            .map_or(self.default, |&(_, level)| level)
+    }
+
+    fn max_level(&self) -> Option<Level> {
+        self.targets
+            .iter()
+            .map(|&(_, level)| level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    let level = parse_level(level)?;
+                    filter.targets.push((String::from(target), level));
+                }
+                None => filter.default = parse_level(directive)?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+// The most detailed level any target wants, 0 for none. Checking this is all
+// an event costs while tracing is off.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static FILTER: Mutex<Filter> = Mutex::new(Filter {
+    default: None,
+    targets: Vec::new(),
+});
+
+pub fn set_filter(filter: Filter) {
+    let max = filter.max_level().map_or(0, |l| l as u8);
+    *FILTER.lock().unwrap() = filter;
+    MAX_LEVEL.store(max, Ordering::Relaxed);
+}
+
+/// Sets the filter from `AOC_LOG`, if it is set.
+pub fn init_from_env() -> Result<(), String> {
+    match env::var("AOC_LOG") {
+        Ok(value) => {
+            let filter = value.parse().map_err(|e| format!("AOC_LOG: {}", e))?;
+            set_filter(filter);
+            Ok(())
+        }
+        Err(_) => Ok(()),
+    }
+}
+
+/// Whether any target might want events at `level`.
+#[inline]
+pub fn is_on(level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+/// The day a module path belongs to: its last component.
+fn target(module_path: &str) -> &str {
+    module_path.rsplit("::").next().unwrap_or(module_path)
+}
+
+fn format_event(
+    level: Level,
+    target: &str,
+    message: &str,
+    fields: &[(&str, &dyn fmt::Debug)],
+) -> String {
+    let mut line = format!("[{} {}] {}", level, target, message);
+    for (key, value) in fields {
+        let _ = write!(line, " {}={:?}", key, value);
+    }
+    line
+}
+
+/// Writes an event to stderr if the filter lets it through. Use `event!`
+/// rather than calling this directly, so that nothing is evaluated while
+/// tracing is off.
+pub fn emit(level: Level, module_path: &str, message: &str, fields: &[(&str, &dyn fmt::Debug)]) {
+    let target = target(module_path);
+    let wanted = FILTER.lock().unwrap().level_for(target);
+    if wanted.is_some_and(|wanted| level <= wanted) {
+        let line = format_event(level, target, message, fields);
+        let _ = writeln!(io::stderr().lock(), "{}", line);
+    }
+}
+
+/// Records an event at a level, with a message and `key = value` fields,
+/// shown with their `Debug` formatting:
+///
+/// ```ignore
+/// event!(Debug, "round", round = n, moved = moved);
+/// ```
+///
+/// Neither the message nor the fields are evaluated unless the level is on.
+macro_rules! event {
+    ($level:ident, $message:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::is_on($crate::trace::Level::$level) {
+            $crate::trace::emit(
+                $crate::trace::Level::$level,
+                module_path!(),
+                $message,
+                &[$((stringify!($key), &$value as &dyn ::std::fmt::Debug)),*],
+            );
+        }
+    };
+}
+
+pub(crate) use event;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter: Filter = "info, day11=trace,day22=off".parse().unwrap();
+        assert_eq!(filter.level_for("day11"), Some(Level::Trace));
+        assert_eq!(filter.level_for("day22"), None);
+        assert_eq!(filter.level_for("day23"), Some(Level::Info));
+        assert_eq!(filter.max_level(), Some(Level::Trace));
+
+        let filter: Filter = "day23=debug".parse().unwrap();
+        assert_eq!(filter.level_for("day01"), None);
+        assert_eq!(filter.max_level(), Some(Level::Debug));
+
+        assert_eq!("".parse::<Filter>(), Ok(Filter::default()));
+        assert!("loud".parse::<Filter>().is_err());
+        assert!("day11=loud".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_format_event() {
+        assert_eq!(target("adventofcode_2022_rust::day11"), "day11");
+        assert_eq!(
+            format_event(
+                Level::Debug,
+                "day23",
+                "round",
+                &[("round", &3), ("moved", &[1, 2])]
+            ),
+            "[debug day23] round round=3 moved=[1, 2]"
+        );
+    }
+}
@@ -0,0 +1,443 @@
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+use crate::{day09, day14, day22, day23, Solution};
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const DARK_GRAY: Color = Color::rgb(48, 48, 48);
+    pub const GRAY: Color = Color::rgb(128, 128, 128);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(80, 200, 80);
+    pub const BLUE: Color = Color::rgb(38, 139, 210);
+    pub const YELLOW: Color = Color::rgb(230, 200, 60);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+
+    /// How bright the colour looks, for greyscale output.
+    pub fn luma(&self) -> u8 {
+        let (r, g, b) = (self.r as u32, self.g as u32, self.b as u32);
+        ((299 * r + 587 * g + 114 * b) / 1000) as u8
+    }
+}
+
+/// One picture of a simulation, a colour per cell.
+pub type Frame = Grid<Color>;
+
+/// A frame drawn in puzzle coordinates, which can be anywhere, rather than
+/// grid positions counted from the top left.
+pub struct Canvas {
+    min: Point2,
+    frame: Frame,
+}
+
+impl Canvas {
+    /// A canvas covering `min` to `max`, both included.
+    pub fn new(min: Point2, max: Point2, background: Color) -> Self {
+        let width = (max.x - min.x + 1).max(0) as usize;
+        let height = (max.y - min.y + 1).max(0) as usize;
+        Canvas {
+            min,
+            frame: Grid::new(width, height, background),
+        }
+    }
+
+    /// Colours the cell at `p`, if it is on the canvas.
+    pub fn plot(&mut self, p: Point2, color: Color) {
+        let q = p - self.min;
+        if q.x >= 0 && q.y >= 0 {
+            if let Some(cell) = self.frame.get_mut((q.x as usize, q.y as usize)) {
+                *cell = color;
+            }
+        }
+    }
+
+    pub fn into_frame(self) -> Frame {
+        self.frame
+    }
+}
+
+/// The smallest box holding all of `points`, as its top left and bottom
+/// right corners.
+pub fn bounds(points: impl IntoIterator<Item = Point2>) -> Option<(Point2, Point2)> {
+    points.into_iter().fold(None, |bounds, p| match bounds {
+        None => Some((p, p)),
+        Some((min, max)) => Some((p.pointwise_min(min), p.pointwise_max(max))),
+    })
+}
+
+/// Somewhere for a simulation to send its frames.
+pub trait Sink {
+    /// Whether the next frame will be used. Simulations ask before drawing
+    /// each one, so that frames nobody wants cost nothing.
+    fn wants_frame(&mut self) -> bool {
+        true
+    }
+
+    fn push(&mut self, frame: &Frame) -> io::Result<()>;
+
+    /// Called once the simulation is over.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for Box<S> {
+    fn wants_frame(&mut self) -> bool {
+        (**self).wants_frame()
+    }
+
+    fn push(&mut self, frame: &Frame) -> io::Result<()> {
+        (**self).push(frame)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        (**self).finish()
+    }
+}
+
+/// Draws a frame with `draw` and sends it to `sink`, if it wants one.
+pub fn offer(sink: &mut dyn Sink, draw: impl FnOnce() -> Frame) -> io::Result<()> {
+    if sink.wants_frame() {
+        sink.push(&draw())
+    } else {
+        Ok(())
+    }
+}
+
+/// Passes every `n`th frame on to another sink, and drops the rest.
+pub struct Every<S> {
+    inner: S,
+    n: usize,
+    count: usize,
+}
+
+impl<S: Sink> Every<S> {
+    pub fn new(inner: S, n: usize) -> Self {
+        Every {
+            inner,
+            n: n.max(1),
+            count: 0,
+        }
+    }
+}
+
+impl<S: Sink> Sink for Every<S> {
+    // `is_multiple_of` would need Rust 1.87.
+    #[allow(clippy::manual_is_multiple_of)]
+    fn wants_frame(&mut self) -> bool {
+        let wanted = self.count % self.n == 0;
+        self.count += 1;
+        wanted && self.inner.wants_frame()
+    }
+
+    fn push(&mut self, frame: &Frame) -> io::Result<()> {
+        self.inner.push(frame)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.inner.finish()
+    }
+}
+
+/// Draws frames in place in a terminal, two cells to a character using
+/// half blocks and 24-bit ANSI colours.
+pub struct Terminal<W: Write> {
+    out: W,
+    delay: Duration,
+    started: bool,
+}
+
+impl<W: Write> Terminal<W> {
+    /// Draws on `out`, pausing for `delay` after each frame.
+    pub fn new(out: W, delay: Duration) -> Self {
+        Terminal {
+            out,
+            delay,
+            started: false,
+        }
+    }
+}
+
+fn write_ansi(out: &mut impl Write, frame: &Frame) -> io::Result<()> {
+    let background = Color::BLACK;
+    for y in (0..frame.height()).step_by(2) {
+        for x in 0..frame.width() {
+            let top = frame[(x, y)];
+            let bottom = frame.get((x, y + 1)).copied().unwrap_or(background);
+            write!(
+                out,
+                "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m\u{2580}",
+                top.r, top.g, top.b, bottom.r, bottom.g, bottom.b
+            )?;
+        }
+        writeln!(out, "\x1b[0m")?;
+    }
+    Ok(())
+}
+
+impl<W: Write> Sink for Terminal<W> {
+    fn push(&mut self, frame: &Frame) -> io::Result<()> {
+        // Clear the screen once, then draw each frame over the last.
+        if !self.started {
+            write!(self.out, "\x1b[2J")?;
+            self.started = true;
+        }
+        let mut buffer = Vec::new();
+        write!(buffer, "\x1b[H")?;
+        write_ansi(&mut buffer, frame)?;
+        self.out.write_all(&buffer)?;
+        self.out.flush()?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImageFormat {
+    Ppm,
+    Pgm,
+    Svg,
+}
+
+impl ImageFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Pgm => "pgm",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Writes binary PPM, with each cell `scale` pixels square.
+pub fn write_ppm(out: &mut impl Write, frame: &Frame, scale: usize) -> io::Result<()> {
+    write_netpbm(out, frame, scale, "P6", |c| vec![c.r, c.g, c.b])
+}
+
+/// Writes binary PGM, the greyscale version of PPM.
+pub fn write_pgm(out: &mut impl Write, frame: &Frame, scale: usize) -> io::Result<()> {
+    write_netpbm(out, frame, scale, "P5", |c| vec![c.luma()])
+}
+
+fn write_netpbm(
+    out: &mut impl Write,
+    frame: &Frame,
+    scale: usize,
+    magic: &str,
+    pixel: impl Fn(Color) -> Vec<u8>,
+) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    writeln!(out, "{}\n{} {}\n255", magic, width, height)?;
+    for row in frame.rows() {
+        let mut line = Vec::new();
+        for &color in row {
+            let pixel = pixel(color);
+            for _ in 0..scale {
+                line.extend_from_slice(&pixel);
+            }
+        }
+        for _ in 0..scale {
+            out.write_all(&line)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes an SVG with a rectangle for each run of cells of the same colour
+/// along a row, `scale` units high and `scale` units wide per cell.
+pub fn write_svg(out: &mut impl Write, frame: &Frame, scale: usize) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+         viewBox=\"0 0 {0} {1}\" shape-rendering=\"crispEdges\">",
+        width, height
+    )?;
+    for (y, row) in frame.rows().enumerate() {
+        let row: Vec<Color> = row.copied().collect();
+        let mut x = 0;
+        while x < row.len() {
+            let run = row[x..].iter().take_while(|&&c| c == row[x]).count();
+            let c = row[x];
+            writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+                x * scale,
+                y * scale,
+                run * scale,
+                scale,
+                c.r,
+                c.g,
+                c.b
+            )?;
+            x += run;
+        }
+    }
+    writeln!(out, "</svg>")
+}
+
+/// Writes each frame to its own numbered file in a directory, as in
+/// `frame-00000.ppm`.
+pub struct ImageSequence {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    count: usize,
+}
+
+impl ImageSequence {
+    pub fn new(dir: PathBuf, format: ImageFormat, scale: usize) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(ImageSequence {
+            dir,
+            format,
+            scale: scale.max(1),
+            count: 0,
+        })
+    }
+}
+
+impl Sink for ImageSequence {
+    fn push(&mut self, frame: &Frame) -> io::Result<()> {
+        let name = format!("frame-{:05}.{}", self.count, self.format.extension());
+        let mut out = BufWriter::new(fs::File::create(self.dir.join(name))?);
+        match self.format {
+            ImageFormat::Ppm => write_ppm(&mut out, frame, self.scale)?,
+            ImageFormat::Pgm => write_pgm(&mut out, frame, self.scale)?,
+            ImageFormat::Svg => write_svg(&mut out, frame, self.scale)?,
+        }
+        self.count += 1;
+        out.flush()
+    }
+}
+
+/// A day whose simulation can be watched.
+pub trait Visualize: Solution {
+    fn visualize(parsed: &Self::Parsed, sink: &mut dyn Sink) -> io::Result<()>;
+}
+
+type VisualizeFn = fn(&str, &mut dyn Sink) -> Result<(), Error>;
+
+/// A day that can be watched, with its solution erased as in the runner's
+/// table of days.
+pub struct Visual {
+    pub number: u8,
+    visualize: VisualizeFn,
+}
+
+impl Visual {
+    const fn of<V: Visualize>(number: u8) -> Visual {
+        Visual {
+            number,
+            visualize: visualize::<V>,
+        }
+    }
+
+    /// Parses `input` and plays the day's simulation into `sink`.
+    pub fn run(&self, input: &str, sink: &mut dyn Sink) -> Result<(), Error> {
+        (self.visualize)(input, sink)
+    }
+}
+
+fn visualize<V: Visualize>(input: &str, sink: &mut dyn Sink) -> Result<(), Error> {
+    let parsed = V::parse(input)?;
+    V::visualize(&parsed, sink)?;
+    sink.finish()?;
+    Ok(())
+}
+
+pub const VISUALS: &[Visual] = &[
+    Visual::of::<day09::Day09>(9),
+    Visual::of::<day14::Day14>(14),
+    Visual::of::<day22::Day22>(22),
+    Visual::of::<day23::Day23>(23),
+];
+
+pub fn find_visual(number: u8) -> Option<&'static Visual> {
+    VISUALS.iter().find(|v| v.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keeps every frame it is given.
+    struct Frames(Vec<Frame>);
+
+    impl Sink for Frames {
+        fn push(&mut self, frame: &Frame) -> io::Result<()> {
+            self.0.push(frame.clone());
+            Ok(())
+        }
+    }
+
+    fn sample() -> Frame {
+        let mut canvas = Canvas::new(Point2::new(-1, -1), Point2::new(1, 0), Color::BLACK);
+        canvas.plot(Point2::new(-1, -1), Color::RED);
+        canvas.plot(Point2::new(0, -1), Color::RED);
+        canvas.plot(Point2::new(1, 0), Color::WHITE);
+        canvas.plot(Point2::new(5, 5), Color::WHITE);
+        canvas.into_frame()
+    }
+
+    #[test]
+    fn test_images() {
+        let frame = sample();
+        assert_eq!((frame.width(), frame.height()), (3, 2));
+
+        let mut ppm = Vec::new();
+        write_ppm(&mut ppm, &frame, 1).unwrap();
+        assert_eq!(&ppm[..11], b"P6\n3 2\n255\n");
+        assert_eq!(&ppm[11..17], &[220, 50, 47, 220, 50, 47]);
+        assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+
+        let mut pgm = Vec::new();
+        write_pgm(&mut pgm, &frame, 2).unwrap();
+        assert_eq!(&pgm[..11], b"P5\n6 4\n255\n");
+        assert_eq!(pgm.len(), 11 + 6 * 4);
+        assert_eq!(pgm[pgm.len() - 1], 255);
+
+        let mut svg = Vec::new();
+        write_svg(&mut svg, &frame, 10).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"20\" height=\"10\" fill=\"#dc322f\"/>"));
+        assert_eq!(svg.matches("<rect").count(), 4);
+    }
+
+    #[test]
+    fn test_visuals() {
+        let examples = [
+            (9, include_str!("../data/examples/day09.txt")),
+            (14, include_str!("../data/examples/day14.txt")),
+            (22, include_str!("../data/examples/day22.txt")),
+            (23, include_str!("../data/examples/day23.txt")),
+        ];
+        for (day, example) in examples {
+            let mut frames = Every::new(Frames(Vec::new()), 2);
+            find_visual(day).unwrap().run(example, &mut frames).unwrap();
+            let frames = frames.inner.0;
+            assert!(!frames.is_empty(), "day {}", day);
+            let size = (frames[0].width(), frames[0].height());
+            assert!(frames
+                .iter()
+                .all(|f| (f.width(), f.height()) == size && f.width() > 0));
+        }
+    }
+}